@@ -0,0 +1,116 @@
+use crate::rng::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Round,
+    Truncate,
+}
+
+impl Rounding {
+    pub const ALL: [Rounding; 2] = [Rounding::Round, Rounding::Truncate];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rounding::Round => "Round",
+            Rounding::Truncate => "Truncate",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dither {
+    None,
+    Rectangular,
+    Triangular,
+}
+
+impl Dither {
+    pub const ALL: [Dither; 3] = [Dither::None, Dither::Rectangular, Dither::Triangular];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dither::None => "No dither",
+            Dither::Rectangular => "Rectangular dither (±½ LSB)",
+            Dither::Triangular => "Triangular dither (±1 LSB)",
+        }
+    }
+}
+
+/// The analog to digital converter quantizing the samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Adc {
+    pub enabled: bool,
+    pub bits: u32,
+    /// the converter covers [-full_scale, full_scale], anything beyond is clipped
+    pub full_scale: f32,
+    pub rounding: Rounding,
+    pub dither: Dither,
+    pub seed: u64,
+}
+
+impl Default for Adc {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bits: 4,
+            full_scale: 1.0,
+            rounding: Rounding::Round,
+            dither: Dither::None,
+            seed: 1,
+        }
+    }
+}
+
+impl Adc {
+    /// The size of one quantization step.
+    pub fn lsb(&self) -> f32 {
+        2.0 * self.full_scale / 2f32.powi(self.bits as i32)
+    }
+
+    /// The lowest and highest output code.
+    fn code_range(&self) -> (f32, f32) {
+        let half = 2f32.powi(self.bits as i32 - 1);
+        (-half, half - 1.0)
+    }
+
+    /// The values the converter can output within `[min, max]`, from lowest to highest.
+    pub fn levels_between(&self, min: f32, max: f32) -> impl Iterator<Item = f32> {
+        let (min_code, max_code) = self.code_range();
+        let lsb = self.lsb();
+        let first = (min / lsb).ceil().max(min_code) as i64;
+        let last = (max / lsb).floor().min(max_code) as i64;
+        (first..=last).map(move |code| code as f32 * lsb)
+    }
+
+    /// Quantizes `samples`, sample `i` always receives the same dither regardless of
+    /// the length of `samples`.
+    pub fn apply(&self, samples: &mut [f32]) {
+        if !self.enabled {
+            return;
+        }
+
+        let lsb = self.lsb();
+        let (min_code, max_code) = self.code_range();
+        let mut rng = Rng::new(self.seed);
+
+        for y in samples.iter_mut() {
+            let dither = match self.dither {
+                Dither::None => 0.0,
+                Dither::Rectangular => rng.uniform() - 0.5,
+                // the sum of two rectangular distributions
+                Dither::Triangular => rng.uniform() - rng.uniform(),
+            };
+            let x = *y / lsb + dither;
+            let code = match self.rounding {
+                Rounding::Round => x.round(),
+                Rounding::Truncate => x.floor(),
+            };
+            *y = code.clamp(min_code, max_code) * lsb;
+        }
+    }
+
+    /// The SQNR of a full-scale sine in dB, `6.02 * N + 1.76`.
+    pub fn theoretical_sqnr_db(&self) -> f32 {
+        6.02 * self.bits as f32 + 1.76
+    }
+}
@@ -2,6 +2,61 @@ use egui::{self, Color32, Stroke, vec2};
 use egui_typed_input::ValText;
 use rustfft::{FftPlanner, num_complex::Complex};
 use std::f32::consts::PI;
+use std::sync::Arc;
+
+use crate::adc::{Adc, Dither, Rounding};
+use crate::expr::Expression;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::file_browser::FileBrowser;
+use crate::filter::{AntiAliasFilter, FilterKind};
+use crate::jitter::{Jitter, JitterKind};
+use crate::multirate::{Multirate, ResamplingFilter};
+use crate::noise::{Noise, NoiseKind};
+use crate::reconstruction::{Reconstruction, ReconstructionMethod};
+use crate::sampling::{Sampling, SamplingMode};
+use crate::signal::{
+    ChirpSweep, IqTone, ModulationKind, Signal, SignalComponent, SignalKind, WavSource, Waveform,
+    alias_frequency, alias_range, wrap_frequency,
+};
+use crate::spectrogram::{Spectrogram, colormap};
+use crate::wav;
+use crate::window::{FftWindow, WindowKind, WindowMetrics};
+
+/// how many harmonics of a non-sinusoidal waveform get an alias marker in the FFT plot
+const MARKED_HARMONICS: u32 = 25;
+
+/// How many samples and frequencies the non-uniform DFT uses at most, its cost is their product
+const NUDFT_MAX_SAMPLES: usize = 2048;
+const NUDFT_FREQUENCIES: usize = 512;
+
+/// The FFT reconstruction sums every bin for every pixel, beyond this many bins the truncated
+/// sinc is used instead
+const MAX_FFT_RECONSTRUCTION_SIZE: usize = 8192;
+
+/// The height in points the plots keep when many are shown, the panel scrolls instead.
+const MIN_PLOT_HEIGHT: f32 = 120.0;
+
+/// Maps the time `t` (in seconds) to the horizontal offset from the left of `rect`,
+/// which shows the window `[0, window_duration)`.
+fn x_offset(rect: egui::Rect, t: f32, window_duration: f32) -> f32 {
+    t / window_duration * rect.width()
+}
+
+/// A "nice" step (1, 2 or 5 times a power of ten) to divide `range` into at most `max_ticks` intervals.
+fn tick_step(range: f32, max_ticks: u32) -> f32 {
+    let raw_step = range / max_ticks as f32;
+    let magnitude = 10f32.powf(raw_step.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Maps `y` from `y_range` (min, max) to the vertical offset from the top of `rect`.
+fn y_offset(rect: egui::Rect, y: f32, (min_y, max_y): (f32, f32)) -> f32 {
+    rect.height() * (max_y - y) / (max_y - min_y)
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FFTSize {
@@ -9,13 +64,145 @@ pub enum FFTSize {
     Custom(u32),
 }
 
+/// The vertical axis of the FFT plot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MagnitudeScale {
+    Linear,
+    /// relative to a full-scale sine, the ADC's full scale or an amplitude of 1
+    Dbfs,
+    /// relative to 1 V RMS
+    Dbv,
+    /// power spectral density in V²/Hz, in dB
+    Psd,
+}
+
+impl MagnitudeScale {
+    pub const ALL: [MagnitudeScale; 4] = [
+        MagnitudeScale::Linear,
+        MagnitudeScale::Dbfs,
+        MagnitudeScale::Dbv,
+        MagnitudeScale::Psd,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MagnitudeScale::Linear => "Linear amplitude",
+            MagnitudeScale::Dbfs => "dBFS",
+            MagnitudeScale::Dbv => "dBV",
+            MagnitudeScale::Psd => "PSD (V²/Hz)",
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            MagnitudeScale::Linear => "",
+            MagnitudeScale::Dbfs => "dBFS",
+            MagnitudeScale::Dbv => "dBV",
+            MagnitudeScale::Psd => "dB(V²/Hz)",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaddingPlacement {
+    End,
+    Centered,
+}
+
+/// Zeros appended to the FFT input, interpolating the spectrum between the bins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZeroPadding {
+    /// the padded length over the number of samples
+    factor: u32,
+    placement: PaddingPlacement,
+}
+
+impl Default for ZeroPadding {
+    fn default() -> Self {
+        Self {
+            factor: 1,
+            placement: PaddingPlacement::End,
+        }
+    }
+}
+
+/// The phase of the FFT bins versus frequency.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhasePlot {
+    enabled: bool,
+    /// in dB relative to the strongest bin, the phase of weaker bins is only that of
+    /// noise and leakage and isn't shown
+    threshold_db: f32,
+    /// removes the jumps by 2π between neighbouring shown bins
+    unwrap: bool,
+}
+
+impl Default for PhasePlot {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_db: -40.0,
+            unwrap: false,
+        }
+    }
+}
+
+/// The spectrum of the continuous signal repeated at every multiple of the sampling frequency.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplicaPlot {
+    enabled: bool,
+    /// how many multiples of the sampling frequency are shown on either side of 0
+    periods: u32,
+}
+
+impl Default for ReplicaPlot {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            periods: 2,
+        }
+    }
+}
+
+/// Limits the number of points the spectrum of the continuous signal is calculated from,
+/// the replica plot spans less than the requested periods once they don't fit
+const MAX_CONTINUOUS_POINTS: usize = 65536;
+
 pub struct AliasApp {
-    signal_frequency: f32,
+    signal: Signal,
     sampling_frequency: f32,
-    /// the offset of the signal, between 0 and 2 (must be multiplied with π)
-    offset: f32,
+    /// the length of the observed time window in seconds, shown by the time plots
+    /// and analyzed by the FFT
+    window_duration: f32,
+    noise: Noise,
+    jitter: Jitter,
+    adc: Adc,
+    filter: AntiAliasFilter,
+    sampling: Sampling,
+    reconstruction: Reconstruction,
+    multirate: Multirate,
+    /// the user placed sample that is being dragged in the sample points plot, and the instant
+    /// it is dragged to. It only moves when released, so that the drag doesn't recalculate
+    /// everything that depends on the sampling on every frame.
+    dragged_sample: Option<(usize, f32)>,
+
+    /// the path typed into the WAV file field
+    wav_path: String,
+    /// why the last WAV file couldn't be loaded
+    wav_error: Option<String>,
+    /// picks the WAV file on the targets without drag and drop or a native file dialog
+    #[cfg(not(target_arch = "wasm32"))]
+    file_browser: FileBrowser,
 
     fft_size: FFTSize,
+    fft_window: FftWindow,
+    zero_padding: ZeroPadding,
+    spectrogram: Spectrogram,
+    phase_plot: PhasePlot,
+    replica_plot: ReplicaPlot,
+    magnitude_scale: MagnitudeScale,
+    /// the bottom of the FFT plot in the dB scales
+    db_floor: f32,
 
     planner: FftPlanner<f32>,
 
@@ -26,37 +213,59 @@ pub struct AliasApp {
     frame_count: u64,
 }
 
+/// The inputs most memos share, everything that changes the samples.
+/// Built once per frame, see [`AliasApp::memo_key`].
+#[derive(Clone, Default, PartialEq)]
+pub struct MemoKey {
+    window_duration: f32,
+    sampling_frequency: f32,
+    signal: Signal,
+    noise: Noise,
+    jitter: Jitter,
+    adc: Adc,
+    filter: AntiAliasFilter,
+    sampling: Sampling,
+}
+
 #[derive(Default)]
 pub struct FFTMemoization {
     // input
-    sampling_frequency: f32,
-    signal_frequency: f32,
-    offset: f32,
+    key: MemoKey,
     fft_signal_size: usize,
+    fft_window: FftWindow,
+    zero_padding: ZeroPadding,
 
     // output
+    /// without window, to reconstruct the signal from
     fft_output: Vec<Complex<f32>>,
+    /// windowed, to display
+    spectrum_output: Vec<Complex<f32>>,
+    /// windowed and zero padded, empty without padding
+    padded_spectrum_output: Vec<Complex<f32>>,
+    /// only measured with jitter enabled
+    measured_snr_db: Option<f32>,
+    /// only measured with the ADC enabled
+    measured_sqnr_db: Option<f32>,
 }
 
 #[derive(Clone, Default)]
 pub struct ReconstructedSignalMemoization {
     // input
+    key: MemoKey,
     horizontal_pixels: u32,
-    sampling_frequency: f32,
-    signal_frequency: f32,
-    offset: f32,
+    reconstruction: Reconstruction,
     fft_len: usize,
 
-    // output
-    reconstructed_signal_output: Vec<(f32, f32)>,
+    // output, complex so that IQ signals keep their quadrature part
+    reconstructed_signal_output: Vec<(f32, Complex<f32>)>,
 }
 
 #[derive(Clone, Default)]
 pub struct SignalMemoization {
     // input
+    window_duration: f32,
     horizontal_pixels: u32,
-    signal_frequency: f32,
-    offset: f32,
+    signal: Signal,
 
     // output
     signal_output: Vec<(f32, f32)>,
@@ -65,29 +274,105 @@ pub struct SignalMemoization {
 #[derive(Clone, Default)]
 pub struct SamplePointsMemoization {
     // input
-    sampling_frequency: f32,
-    signal_frequency: f32,
-    offset: f32,
+    key: MemoKey,
 
     // output
     sample_points_output: Vec<(f32, f32)>,
 }
 
+#[derive(Clone, Default)]
+pub struct NudftMemoization {
+    // input
+    key: MemoKey,
+    n_samples: usize,
+    freq_range: (f32, f32),
+
+    // output, (frequency, magnitude / N)
+    nudft_output: Vec<(f32, f32)>,
+}
+
+#[derive(Clone, Default)]
+pub struct MultirateMemoization {
+    // input
+    key: MemoKey,
+    multirate: Multirate,
+    n_samples: usize,
+
+    // output
+    output: Vec<Complex<f32>>,
+}
+
+#[derive(Clone, Default)]
+pub struct SpectrogramMemoization {
+    // input
+    key: MemoKey,
+    spectrogram: Spectrogram,
+    n_samples: usize,
+
+    // output, one column of dB values per segment
+    spectrogram_output: Vec<Vec<f32>>,
+}
+
+#[derive(Clone, Default)]
+pub struct ContinuousSpectrumMemoization {
+    // input
+    window_duration: f32,
+    sampling_frequency: f32,
+    signal: Signal,
+    filter: AntiAliasFilter,
+    periods: u32,
+
+    // output, (frequency, amplitude) in ascending frequency
+    spectrum_output: Vec<(f32, f32)>,
+}
+
 #[derive(Default)]
 pub struct AliasAppMemoization {
+    /// the shared inputs of this frame
+    key: MemoKey,
     fft: Option<FFTMemoization>,
     reconstructed_signal: Option<ReconstructedSignalMemoization>,
     signal: Option<SignalMemoization>,
     sample_points: Option<SamplePointsMemoization>,
+    // the quadrature parts of complex signals
+    quadrature_signal: Option<SignalMemoization>,
+    quadrature_sample_points: Option<SamplePointsMemoization>,
+    nudft: Option<NudftMemoization>,
+    // the samples after the rate change and their spectrum
+    resampled: Option<MultirateMemoization>,
+    resampled_fft: Option<MultirateMemoization>,
+    window_metrics: Option<(FftWindow, WindowMetrics)>,
+    filter_poles: Option<(AntiAliasFilter, Vec<Complex<f64>>)>,
+    spectrogram: Option<SpectrogramMemoization>,
+    continuous_spectrum: Option<ContinuousSpectrumMemoization>,
 }
 
 impl Default for AliasApp {
     fn default() -> Self {
         Self {
-            signal_frequency: 3.0,
+            signal: Signal::default(),
             sampling_frequency: 10.0,
-            offset: 0.0,
+            window_duration: 1.0,
+            noise: Noise::default(),
+            jitter: Jitter::default(),
+            adc: Adc::default(),
+            filter: AntiAliasFilter::default(),
+            sampling: Sampling::default(),
+            reconstruction: Reconstruction::default(),
+            multirate: Multirate::default(),
+            dragged_sample: None,
+            wav_path: String::new(),
+            wav_error: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            file_browser: FileBrowser::new("wav"),
             fft_size: FFTSize::Auto,
+            fft_window: FftWindow::default(),
+            zero_padding: ZeroPadding::default(),
+            spectrogram: Spectrogram::default(),
+            phase_plot: PhasePlot::default(),
+            replica_plot: ReplicaPlot::default(),
+            magnitude_scale: MagnitudeScale::Linear,
+            db_floor: -100.0,
             planner: FftPlanner::new(),
             // manual memoization
             memo: AliasAppMemoization::default(),
@@ -114,173 +399,368 @@ impl AliasApp {
         #[cfg(not(target_arch = "wasm32"))]
         let render_start_time = std::time::Instant::now();
 
+        self.load_dropped_files(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             // Set dark mode
             ui.ctx().set_visuals(egui::Visuals::dark());
 
             self.render_sliders(ui);
 
-            let horizontal_pixels = (ctx.pixels_per_point() * ui.available_width()) as u32;
+            // the plots share the height left below the controls, and scroll once they would
+            // get smaller than MIN_PLOT_HEIGHT
+            let plots_height = ui.available_height();
+            egui::ScrollArea::vertical()
+                .id_salt("plots")
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    // band-limited waveforms only keep the harmonics below Nyquist
+                    self.signal.band_limit = self.sampling_frequency / 2.0;
+                    // a chirp sweeps once across the observed window
+                    self.signal.chirp.duration = self.window_duration;
+                    self.memo.key = self.memo_key();
+
+                    let horizontal_pixels = (ctx.pixels_per_point() * ui.available_width()) as u32;
+
+                    // Generate signal points
+                    let signal = self.calculate_signal(horizontal_pixels);
+
+                    // Generate sample points
+                    let sample_points = self.calculate_sample_points();
+
+                    // the quadrature part of a complex signal shares the plots with the in-phase part
+                    let (quadrature_signal, quadrature_sample_points) = if self.signal.is_complex() {
+                        (
+                            self.calculate_quadrature_signal(horizontal_pixels),
+                            self.calculate_quadrature_sample_points(),
+                        )
+                    } else {
+                        (Vec::new(), Vec::new())
+                    };
+
+                    // Vertical range shared by all time-domain plots, always including the zero line
+                    let y_range = {
+                        let (min_y, max_y) = signal
+                            .iter()
+                            .chain(sample_points.iter())
+                            .chain(quadrature_signal.iter())
+                            .chain(quadrature_sample_points.iter())
+                            .fold((0.0f32, 0.0f32), |(min_y, max_y), (_, y)| {
+                                (min_y.min(*y), max_y.max(*y))
+                            });
+                        if max_y - min_y < 1e-6 {
+                            (-1.0, 1.0)
+                        } else {
+                            let margin = 0.1 * (max_y - min_y);
+                            (min_y - margin, max_y + margin)
+                        }
+                    };
 
-            // Generate signal points
-            let signal = self.calculate_signal(horizontal_pixels);
+                    // Calculate total height needed for all plots, the reconstruction error is half a plot
+                    // and the rate change adds another pair
+                    let n_plots = 4.5
+                        + if self.phase_plot.enabled { 1.0 } else { 0.0 }
+                        + if self.spectrogram.enabled { 1.0 } else { 0.0 }
+                        + if self.replica_plot.enabled { 1.0 } else { 0.0 }
+                        + if self.multirate.enabled { 2.0 } else { 0.0 };
+                    // plots with spacing
+                    let plot_height = (plots_height / n_plots - 60.0).max(MIN_PLOT_HEIGHT);
+                    let plot_width = ui.available_width();
+
+                    // Helper function to draw axis labels
+                    let draw_axis_labels =
+                        |painter: &egui::Painter, rect: egui::Rect, x_label: &str, y_label: &str| {
+                            // X-axis label
+                            painter.text(
+                                egui::Pos2::new(rect.right() - 40.0, rect.bottom() + 15.0),
+                                egui::Align2::CENTER_CENTER,
+                                x_label,
+                                egui::FontId::proportional(14.0),
+                                Color32::YELLOW,
+                            );
+
+                            // Y-axis label
+                            painter.text(
+                                egui::Pos2::new(rect.left() - 25.0, rect.center().y),
+                                egui::Align2::CENTER_CENTER,
+                                y_label,
+                                egui::FontId::proportional(14.0),
+                                Color32::YELLOW,
+                            );
+                        };
+
+                    // Helper function to draw a separator line
+                    let draw_separator = |ui: &mut egui::Ui| {
+                        let separator_height = 2.0;
+                        let separator_color = Color32::from_rgb(100, 100, 100); // Dark gray
+
+                        let response = ui.allocate_rect(
+                            egui::Rect::from_min_size(
+                                ui.cursor().min,
+                                egui::Vec2::new(ui.available_width(), separator_height),
+                            ),
+                            egui::Sense::hover(),
+                        );
+
+                        let rect = response.rect;
+                        ui.painter().rect_filled(rect, 0.0, separator_color);
+
+                        ui.add_space(5.0); // Space after separator
+                    };
 
-            // Generate sample points
-            let sample_points = self.calculate_sample_points();
+                    // 1. Original signal with vertical lines at sample points
+                    self.render_signal_graph(
+                        ui,
+                        &signal,
+                        &sample_points,
+                        plot_height,
+                        plot_width,
+                        y_range,
+                        draw_axis_labels,
+                    );
+                    ui.add_space(5.0);
+                    draw_separator(ui);
+
+                    // 2. Sample points only
+                    self.render_sample_points_graph(
+                        ui,
+                        sample_points,
+                        plot_height,
+                        plot_width,
+                        y_range,
+                        draw_axis_labels,
+                    );
+                    ui.add_space(5.0);
+                    draw_separator(ui);
+
+                    // 3. FFT of sampled points
+                    let fft_output = self.calculate_fft();
+                    let spectrum = self.calculate_spectrum();
+                    let padded_spectrum = self.calculate_padded_spectrum();
+                    let fft_size = spectrum.len();
+                    let freq_resolution = self.sampling_frequency / fft_size as f32;
+                    // ui.colored_label(
+                    //     Color32::YELLOW,
+                    //     format!("FFT(n={fft_size}, resolution={freq_resolution:.4} Hz)"),
+                    // );
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!("FFT(n={fft_size}, resolution={freq_resolution:.4} Hz)"),
+                        );
+                        if !padded_spectrum.is_empty() {
+                            ui.colored_label(
+                                Color32::YELLOW,
+                                format!(
+                                    "padded to {}, bin spacing {:.4} Hz",
+                                    padded_spectrum.len(),
+                                    self.sampling_frequency / padded_spectrum.len() as f32
+                                ),
+                            );
+                        }
 
-            // Constants for all plots
-            let _max_y = 1.0;
-            let _min_y = -1.0;
-            // let y_range = max_y - min_y;
+                        if ui.small_button("raw values").clicked() {
+                            self.show_raw_fft_values = !self.show_raw_fft_values;
+                        }
 
-            // Calculate total height needed for all plots
-            let plot_height = ui.available_height() / 4.0 - 60.0; // 4 plots with spacing
-            let plot_width = ui.available_width();
+                        if self.fft_window.kind != WindowKind::Rectangular {
+                            let metrics = self.calculate_window_metrics();
+                            ui.colored_label(
+                                Color32::LIGHT_BLUE,
+                                format!(
+                                    "{}: ENBW {:.2} bins, coherent gain {:.3}, scalloping loss {:.2} dB, highest sidelobe {:.1} dB",
+                                    self.fft_window.kind.name(),
+                                    metrics.enbw,
+                                    metrics.coherent_gain,
+                                    metrics.scalloping_loss_db,
+                                    metrics.highest_sidelobe_db
+                                ),
+                            );
+                        }
 
-            // Helper function to draw axis labels
-            let draw_axis_labels =
-                |painter: &egui::Painter, rect: egui::Rect, x_label: &str, y_label: &str| {
-                    // X-axis label
-                    painter.text(
-                        egui::Pos2::new(rect.right() - 40.0, rect.bottom() + 15.0),
-                        egui::Align2::CENTER_CENTER,
-                        x_label,
-                        egui::FontId::proportional(14.0),
-                        Color32::YELLOW,
-                    );
+                        if let Some(measured_snr_db) = self.measured_snr_db() {
+                            // the jitter limit is worst for the highest frequency
+                            let max_line_freq = self
+                                .signal
+                                .spectral_lines()
+                                .iter()
+                                .map(|line| line.frequency.abs())
+                                .fold(0.0, f32::max);
+                            // the slope of the signal is at most that of a full-scale sine at the
+                            // highest frequency, so its SNR can only be better
+                            let predicted = if max_line_freq > 0.0 {
+                                format!(
+                                    "bound -20·log10(2π·f·σ) ≥ {:.1} dB at {max_line_freq:.1} Hz, ",
+                                    self.jitter.snr_limit_db(max_line_freq)
+                                )
+                            } else {
+                                String::new()
+                            };
+                            ui.colored_label(
+                                Color32::LIGHT_BLUE,
+                                format!("Jitter SNR: {predicted}measured {measured_snr_db:.1} dB"),
+                            );
+                        }
 
-                    // Y-axis label
-                    painter.text(
-                        egui::Pos2::new(rect.left() - 25.0, rect.center().y),
-                        egui::Align2::CENTER_CENTER,
-                        y_label,
-                        egui::FontId::proportional(14.0),
-                        Color32::YELLOW,
+                        if let Some(measured_sqnr_db) = self.measured_sqnr_db() {
+                            ui.colored_label(
+                                Color32::LIGHT_BLUE,
+                                format!(
+                                    "SQNR: measured {measured_sqnr_db:.1} dB, full-scale sine 6.02·N+1.76 = {:.1} dB",
+                                    self.adc.theoretical_sqnr_db()
+                                ),
+                            );
+                        }
+                    });
+                    let response3 = ui.allocate_rect(
+                        egui::Rect::from_min_size(
+                            ui.cursor().min,
+                            egui::Vec2::new(plot_width, plot_height),
+                        ),
+                        egui::Sense::hover(),
                     );
-                };
 
-            // Helper function to draw a separator line
-            let draw_separator = |ui: &mut egui::Ui| {
-                let separator_height = 2.0;
-                let separator_color = Color32::from_rgb(100, 100, 100); // Dark gray
+                    let rect = response3.rect.intersect(ui.clip_rect());
+                    let painter = ui.painter();
+
+                    // Define fixed frequency range (0 to 20 Hz)
+                    self.render_fft(
+                        draw_axis_labels,
+                        rect,
+                        painter,
+                        fft_size,
+                        &spectrum,
+                        &padded_spectrum,
+                    );
 
-                let response = ui.allocate_rect(
-                    egui::Rect::from_min_size(
-                        ui.cursor().min,
-                        egui::Vec2::new(ui.available_width(), separator_height),
-                    ),
-                    egui::Sense::hover(),
-                );
+                    if self.show_raw_fft_values {
+                        self.render_fft_raw_values(ctx, ui, &spectrum);
+                    }
 
-                let rect = response.rect;
-                ui.painter().rect_filled(rect, 0.0, separator_color);
+                    ui.add_space(5.0);
+                    draw_separator(ui);
 
-                ui.add_space(5.0); // Space after separator
-            };
+                    // 3b. Phase of the bins
+                    if self.phase_plot.enabled {
+                        self.render_phase(ui, &spectrum, plot_height, plot_width);
+                        ui.add_space(5.0);
+                        draw_separator(ui);
+                    }
 
-            // 1. Original signal with vertical lines at sample points
-            self.render_signal_graph(
-                ui,
-                &signal,
-                &sample_points,
-                plot_height,
-                plot_width,
-                draw_axis_labels,
-            );
-            ui.add_space(5.0);
-            draw_separator(ui);
-
-            // 2. Sample points only
-            self.render_sample_points_graph(
-                ui,
-                sample_points,
-                plot_height,
-                plot_width,
-                draw_axis_labels,
-            );
-            ui.add_space(5.0);
-            draw_separator(ui);
-
-            // 3. FFT of sampled points
-            let fft_output = self.calculate_fft();
-            let fft_size = fft_output.len();
-            let freq_resolution = self.sampling_frequency / fft_size as f32;
-            // ui.colored_label(
-            //     Color32::YELLOW,
-            //     format!("FFT(n={fft_size}, resolution={freq_resolution:.4} Hz)"),
-            // );
-            ui.horizontal(|ui| {
-                ui.colored_label(
-                    Color32::YELLOW,
-                    format!("FFT(n={fft_size}, resolution={freq_resolution:.4} Hz)"),
-                );
+                    // 4. Reconstructed signal
 
-                if ui.small_button("raw values").clicked() {
-                    self.show_raw_fft_values = !self.show_raw_fft_values;
-                }
-            });
-            let response3 = ui.allocate_rect(
-                egui::Rect::from_min_size(
-                    ui.cursor().min,
-                    egui::Vec2::new(plot_width, plot_height),
-                ),
-                egui::Sense::hover(),
-            );
+                    // Create reconstructed signal
+                    let recon_signal = self.calculate_reconstructed_signal(horizontal_pixels, &fft_output);
 
-            let rect = response3.rect.intersect(ui.clip_rect());
-            let painter = ui.painter();
+                    self.render_reconstructed(
+                        ui,
+                        signal,
+                        plot_height,
+                        plot_width,
+                        y_range,
+                        draw_axis_labels,
+                        recon_signal,
+                    );
 
-            // Define fixed frequency range (0 to 20 Hz)
-            self.render_fft(draw_axis_labels, rect, painter, fft_size, &fft_output);
+                    // Add extra space before the aliasing warning
+                    ui.add_space(15.0);
 
-            if self.show_raw_fft_values {
-                self.render_fft_raw_values(ctx, ui, &fft_output);
-            }
+                    // Add aliasing warning in its own area below the plot
+                    if self.signal.max_frequency() >= self.sampling_frequency / 2.0 {
+                        self.render_aliasing_warning(ui);
+                    } else {
+                        // Add some empty space even when there's no warning
+                        ui.add_space(30.0);
+                    }
 
-            ui.add_space(5.0);
-            draw_separator(ui);
+                    // 5. Spectrogram
+                    if self.spectrogram.enabled {
+                        ui.add_space(15.0);
+                        draw_separator(ui);
+                        self.render_spectrogram(ui, plot_height, plot_width);
+                    }
 
-            // 4. Reconstructed signal
+                    // 5b. The replicas of the spectrum around the multiples of fs
+                    if self.replica_plot.enabled {
+                        ui.add_space(15.0);
+                        draw_separator(ui);
+                        self.render_replicas(ui, plot_height, plot_width);
+                    }
 
-            // Create reconstructed signal
-            let recon_signal = self.calculate_reconstructed_signal(horizontal_pixels, &fft_output);
+                    // 6. After the rate change
+                    if self.multirate.enabled {
+                        ui.add_space(15.0);
+                        draw_separator(ui);
+                        self.render_resampled(ui, horizontal_pixels, plot_height, plot_width, y_range);
+                    }
 
-            self.render_reconstructed(
-                ui,
-                signal,
-                plot_height,
-                plot_width,
-                draw_axis_labels,
-                recon_signal,
-            );
+                    ui.add_space(15.0);
+                    draw_separator(ui);
 
-            // Add extra space before the aliasing warning
-            ui.add_space(15.0);
+                    #[cfg(target_arch = "wasm32")]
+                    let time = performance.now() - render_start_time;
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let time = render_start_time.elapsed().as_secs_f32() * 1000.0;
 
-            // Add aliasing warning in its own area below the plot
-            if self.signal_frequency >= self.sampling_frequency / 2.0 {
-                self.render_aliasing_warning(ui);
-            } else {
-                // Add some empty space even when there's no warning
-                ui.add_space(30.0);
-            }
+                    ui.label(format!("Frame {}: {:.2} ms", self.frame_count, time));
+                });
+        });
+    }
+}
 
-            ui.add_space(15.0);
-            draw_separator(ui);
+impl AliasApp {
+    /// Uses a WAV file dropped onto the window (desktop and web) as the signal.
+    fn load_dropped_files(&mut self, ctx: &egui::Context) {
+        for file in ctx.input(|i| i.raw.dropped_files.clone()) {
+            // on the web the content is passed along, natively only the path
+            let bytes = match (&file.bytes, &file.path) {
+                (Some(bytes), _) => bytes.to_vec(),
+                (None, Some(path)) => match std::fs::read(path) {
+                    Ok(bytes) => bytes,
+                    Err(err) => {
+                        self.wav_error = Some(err.to_string());
+                        continue;
+                    }
+                },
+                (None, None) => continue,
+            };
+            let name = match &file.path {
+                Some(path) => path.to_string_lossy().to_string(),
+                None => file.name.clone(),
+            };
+            self.load_wav(&name, &bytes);
+        }
+    }
 
-            #[cfg(target_arch = "wasm32")]
-            let time = performance.now() - render_start_time;
-            #[cfg(not(target_arch = "wasm32"))]
-            let time = render_start_time.elapsed().as_secs_f32() * 1000.0;
+    /// Reads the WAV file at `path` and uses it as the signal.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_wav_file(&mut self, path: &std::path::Path) {
+        match std::fs::read(path) {
+            Ok(bytes) => self.load_wav(&path.to_string_lossy(), &bytes),
+            Err(err) => self.wav_error = Some(err.to_string()),
+        }
+    }
 
-            ui.label(format!("Frame {}: {:.2} ms", self.frame_count, time));
-        });
+    fn load_wav(&mut self, name: &str, bytes: &[u8]) {
+        match wav::decode(bytes) {
+            Ok(wav) => {
+                self.signal.kind = SignalKind::Wav;
+                self.signal.wav = WavSource {
+                    wav: Some(Arc::new(wav)),
+                    name: name.to_string(),
+                    start: 0.0,
+                };
+                self.wav_error = None;
+            }
+            Err(err) => self.wav_error = Some(err),
+        }
     }
 }
 
 impl AliasApp {
-    fn calculate_fft(&mut self) -> Vec<Complex<f32>> {
-        let fft_signal_size = match self.fft_size {
+    /// How many samples the FFT transforms.
+    fn fft_signal_size(&self) -> usize {
+        match self.fft_size {
             FFTSize::Auto => self.calculate_optimal_fft_size(),
             FFTSize::Custom(size) => {
                 let mut s = size as usize;
@@ -289,85 +769,157 @@ impl AliasApp {
                 }
                 s
             }
-        };
+        }
+    }
+
+    fn calculate_fft(&mut self) -> Vec<Complex<f32>> {
+        let fft_signal_size = self.fft_signal_size();
 
         match self.memo.fft {
             Some(ref mut memo)
-                if memo.sampling_frequency == self.sampling_frequency
-                    && memo.signal_frequency == self.signal_frequency
+                if memo.key == self.memo.key
                     && memo.fft_signal_size == fft_signal_size
-                    && memo.offset == self.offset =>
+                    && memo.fft_window == self.fft_window
+                    && memo.zero_padding == self.zero_padding =>
             {
                 // Use cached FFT output
                 memo.fft_output.clone()
             }
             _ => {
                 // Calculate FFT and store in memoization
-                let fft_output = self._calculate_fft(fft_signal_size);
+                let rectangular = FftWindow {
+                    kind: WindowKind::Rectangular,
+                    ..self.fft_window
+                };
+                let no_padding = ZeroPadding::default();
+                let fft_output = self._calculate_fft(fft_signal_size, rectangular, no_padding);
+                let spectrum_output = if self.fft_window == rectangular {
+                    fft_output.clone()
+                } else {
+                    self._calculate_fft(fft_signal_size, self.fft_window, no_padding)
+                };
+                // the placement is kept while unpadded, but only matters with padding
+                let padded_spectrum_output = if self.zero_padding.factor <= 1 {
+                    Vec::new()
+                } else {
+                    self._calculate_fft(fft_signal_size, self.fft_window, self.zero_padding)
+                };
+                let measured_snr_db = self
+                    .jitter
+                    .enabled
+                    .then(|| self._calculate_measured_snr(fft_signal_size))
+                    .flatten();
+                let measured_sqnr_db = self
+                    .adc
+                    .enabled
+                    .then(|| self._calculate_measured_sqnr(fft_signal_size))
+                    .flatten();
                 self.memo.fft = Some(FFTMemoization {
-                    sampling_frequency: self.sampling_frequency,
-                    signal_frequency: self.signal_frequency,
-                    offset: self.offset,
+                    key: self.memo.key.clone(),
                     fft_signal_size,
+                    fft_window: self.fft_window,
+                    zero_padding: self.zero_padding,
                     fft_output: fft_output.clone(),
+                    spectrum_output,
+                    padded_spectrum_output,
+                    measured_snr_db,
+                    measured_sqnr_db,
                 });
                 fft_output
             }
         }
     }
 
+    /// The windowed FFT to display, see [`Self::calculate_fft`].
+    fn calculate_spectrum(&mut self) -> Vec<Complex<f32>> {
+        self.calculate_fft();
+        match self.memo.fft {
+            Some(ref memo) => memo.spectrum_output.clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The windowed and zero padded FFT, see [`Self::calculate_fft`], empty without padding.
+    fn calculate_padded_spectrum(&mut self) -> Vec<Complex<f32>> {
+        self.calculate_fft();
+        match self.memo.fft {
+            Some(ref memo) => memo.padded_spectrum_output.clone(),
+            None => Vec::new(),
+        }
+    }
+
+    fn calculate_window_metrics(&mut self) -> WindowMetrics {
+        if let Some((window, metrics)) = self.memo.window_metrics {
+            if window == self.fft_window {
+                return metrics;
+            }
+        }
+
+        let metrics = self.fft_window.metrics(&mut self.planner);
+        self.memo.window_metrics = Some((self.fft_window, metrics));
+        metrics
+    }
+
+    /// The poles of the anti-aliasing filter, finding those of a Bessel filter is too slow
+    /// to repeat for every frequency the response is drawn at.
+    fn calculate_filter_poles(&mut self) -> Vec<Complex<f64>> {
+        if let Some((filter, ref poles)) = self.memo.filter_poles {
+            if filter == self.filter {
+                return poles.clone();
+            }
+        }
+
+        let poles = self.filter.poles();
+        self.memo.filter_poles = Some((self.filter, poles.clone()));
+        poles
+    }
+
+    /// The samples taken within the observed window, so that the FFT analyzes what the time plots show.
     fn calculate_optimal_fft_size(&self) -> usize {
-        let mut n = (20.0 * self.sampling_frequency) as usize;
+        let mut n = ((self.window_duration * self.sampling_frequency) as usize).max(2);
         if n % 2 != 0 {
             n += 1;
         }
         n
     }
 
-    fn _calculate_fft(&mut self, fft_signal_size: usize) -> Vec<Complex<f32>> {
-        // Use zero-padding at the beginning and end to reduce edge artifacts
-        let n_padding = 0;
-        let fft_size = fft_signal_size + 2 * n_padding;
+    fn _calculate_fft(
+        &mut self,
+        fft_signal_size: usize,
+        window: FftWindow,
+        zero_padding: ZeroPadding,
+    ) -> Vec<Complex<f32>> {
+        // Zero-padding interpolates the spectrum, it doesn't add resolution
+        let fft_size = fft_signal_size * zero_padding.factor.max(1) as usize;
+        let n_padding = match zero_padding.placement {
+            PaddingPlacement::End => 0,
+            PaddingPlacement::Centered => (fft_size - fft_signal_size) / 2,
+        };
 
         let mut fft_input = Vec::with_capacity(fft_size);
 
         // Add zeros at the beginning (pre-padding)
         for _ in 0..n_padding {
-            fft_input.push(0.0);
+            fft_input.push(Complex::new(0.0, 0.0));
         }
 
-        // Generate the signal with precise frequency and phase
-        for i in 0..fft_signal_size {
-            let t = i as f32 / self.sampling_frequency;
-            // Use more precise sine calculation
-            let y = (self.signal_frequency * 2.0 * PI * t + self.offset * PI).sin();
-            fft_input.push(y);
-        }
+        // Sample the signal, including noise
+        fft_input.extend(self.sample_signal_complex(fft_signal_size));
 
         // Add zeros at the end (post-padding)
-        for _ in 0..n_padding {
-            fft_input.push(0.0);
-        }
-
-        // Prepare FFT input
-        let fft_input: Vec<Complex<f32>> =
-            fft_input.iter().map(|y| Complex::new(*y, 0.0)).collect();
+        fft_input.resize(fft_size, Complex::new(0.0, 0.0));
 
         assert!(fft_input.len() == fft_size);
 
-        // // Apply Blackman-Harris window for even better spectral leakage reduction
-        // let window_len = fft_input.len();
-        // for i in 0..window_len {
-        //     // 4-term Blackman-Harris window has even better sidelobe suppression
-        //     let a0 = 0.35875;
-        //     let a1 = 0.48829;
-        //     let a2 = 0.14128;
-        //     let a3 = 0.01168;
-        //     let window = a0 - a1 * (2.0 * PI * i as f32 / (window_len - 1) as f32).cos()
-        //         + a2 * (4.0 * PI * i as f32 / (window_len - 1) as f32).cos()
-        //         - a3 * (6.0 * PI * i as f32 / (window_len - 1) as f32).cos();
-        //     fft_input[i] *= window;
-        // }
+        // Apply the window, divided by its coherent gain so that a tone keeps its amplitude
+        let window = window.coefficients(fft_signal_size);
+        let coherent_gain = window.iter().sum::<f32>() / fft_signal_size as f32;
+        for (x, w) in fft_input[n_padding..n_padding + fft_signal_size]
+            .iter_mut()
+            .zip(&window)
+        {
+            *x *= w / coherent_gain;
+        }
 
         // Perform FFT
         let planner = &mut self.planner;
@@ -376,19 +928,146 @@ impl AliasApp {
         fft.process(&mut fft_output);
         fft_output
     }
+
+    /// The signal to jitter noise ratio in dB of the FFT input, the power of the filtered signal
+    /// over the power of what the jitter changes in it. Noise and quantization are left out of
+    /// both, so that only the jitter counts. None if the jitter doesn't change the samples.
+    fn _calculate_measured_snr(&self, fft_signal_size: usize) -> Option<f32> {
+        let ideal_times = self.sampling.sample_times(
+            fft_signal_size,
+            self.sampling_frequency,
+            self.window_duration,
+        );
+        let jittered_times = self.sample_times(fft_signal_size);
+        // the anti-aliasing filter is part of the signal, not of the noise
+        let complex_values = |times: &[f32]| -> Vec<Complex<f32>> {
+            self.filtered_values(times, Signal::value)
+                .into_iter()
+                .zip(self.filtered_values(times, Signal::quadrature_value))
+                .map(|(i, q)| Complex::new(i, q))
+                .collect()
+        };
+        let ideal = complex_values(&ideal_times);
+        let jittered = complex_values(&jittered_times);
+
+        let n = ideal.len() as f32;
+        // the DC offset doesn't count as signal
+        let mean = ideal.iter().sum::<Complex<f32>>() / n;
+        let signal_power = ideal.iter().map(|y| (y - mean).norm_sqr()).sum::<f32>() / n;
+        let noise_power = ideal
+            .iter()
+            .zip(&jittered)
+            .map(|(ideal, jittered)| (jittered - ideal).norm_sqr())
+            .sum::<f32>()
+            / n;
+        (noise_power > 0.0).then(|| 10.0 * (signal_power / noise_power).log10())
+    }
+
+    /// The SNR measured by the last FFT, see [`Self::_calculate_measured_snr`].
+    fn measured_snr_db(&self) -> Option<f32> {
+        self.memo.fft.as_ref().and_then(|memo| memo.measured_snr_db)
+    }
+
+    /// The signal to quantization noise ratio in dB of the FFT input,
+    /// the power of the signal entering the ADC over the power of the error the ADC adds.
+    /// None if the ADC doesn't change the samples, the ratio would be infinite.
+    fn _calculate_measured_sqnr(&self, fft_signal_size: usize) -> Option<f32> {
+        let analog = self.sample_analog(fft_signal_size);
+        let mut quantized = analog.clone();
+        self.adc.apply(&mut quantized);
+
+        let n = analog.len() as f32;
+        let mean = analog.iter().sum::<f32>() / n;
+        let signal_power = analog.iter().map(|y| (y - mean).powi(2)).sum::<f32>() / n;
+        let error_power = analog
+            .iter()
+            .zip(&quantized)
+            .map(|(a, q)| (q - a).powi(2))
+            .sum::<f32>()
+            / n;
+        (error_power > 0.0).then(|| 10.0 * (signal_power / error_power).log10())
+    }
+
+    /// The SQNR measured by the last FFT, see [`Self::_calculate_measured_sqnr`].
+    fn measured_sqnr_db(&self) -> Option<f32> {
+        self.memo
+            .fft
+            .as_ref()
+            .and_then(|memo| memo.measured_sqnr_db)
+    }
+
+    /// The spectrum of the samples at the instants they were actually taken at, over `freq_range`.
+    ///
+    /// Unlike the FFT, which assumes a uniform grid, this non-uniform DFT shows how irregular
+    /// sampling smears aliases into a noise floor instead of clean alias tones.
+    fn calculate_nudft(&mut self, n_samples: usize, freq_range: (f32, f32)) -> Vec<(f32, f32)> {
+        if let Some(ref memo) = self.memo.nudft {
+            if memo.key == self.memo.key
+                && memo.n_samples == n_samples
+                && memo.freq_range == freq_range
+            {
+                return memo.nudft_output.clone();
+            }
+        }
+
+        let result = self._calculate_nudft(n_samples, freq_range);
+        self.memo.nudft = Some(NudftMemoization {
+            key: self.memo.key.clone(),
+            n_samples,
+            freq_range,
+            nudft_output: result.clone(),
+        });
+        result
+    }
+
+    fn _calculate_nudft(
+        &mut self,
+        n_samples: usize,
+        (min_freq, max_freq): (f32, f32),
+    ) -> Vec<(f32, f32)> {
+        let n_samples = n_samples.min(NUDFT_MAX_SAMPLES);
+        let sample_times = self.sample_times(n_samples);
+        let samples = self.sample_signal_complex(n_samples);
+
+        (0..=NUDFT_FREQUENCIES)
+            .map(|i| {
+                let freq = min_freq + (max_freq - min_freq) * i as f32 / NUDFT_FREQUENCIES as f32;
+                // X(f) = sum_i [ x_i * exp(-j*2π*f*t_i) ]
+                let bin: Complex<f32> = sample_times
+                    .iter()
+                    .zip(&samples)
+                    .map(|(t, x)| x * Complex::from_polar(1.0, -2.0 * PI * freq * t))
+                    .sum();
+                (freq, bin.norm() / n_samples as f32)
+            })
+            .collect()
+    }
 }
 
 impl AliasApp {
+    /// The shared inputs the memos compare, see [`MemoKey`].
+    fn memo_key(&self) -> MemoKey {
+        MemoKey {
+            window_duration: self.window_duration,
+            sampling_frequency: self.sampling_frequency,
+            signal: self.signal.clone(),
+            noise: self.noise,
+            jitter: self.jitter,
+            adc: self.adc,
+            filter: self.filter,
+            sampling: self.sampling.clone(),
+        }
+    }
+
     fn calculate_reconstructed_signal(
         &mut self,
         horizontal_pixels: u32,
         fft_output: &Vec<Complex<f32>>,
-    ) -> Vec<(f32, f32)> {
+    ) -> Vec<(f32, Complex<f32>)> {
         if let Some(ref memo) = self.memo.reconstructed_signal {
             if memo.horizontal_pixels == horizontal_pixels
-                && memo.sampling_frequency == self.sampling_frequency
-                && memo.signal_frequency == self.signal_frequency
-                && memo.offset == self.offset
+                && memo.key == self.memo.key
+                && memo.reconstruction == self.reconstruction
                 && memo.fft_len == fft_output.len()
             {
                 return memo.reconstructed_signal_output.clone();
@@ -399,9 +1078,8 @@ impl AliasApp {
 
         self.memo.reconstructed_signal = Some(ReconstructedSignalMemoization {
             horizontal_pixels,
-            sampling_frequency: self.sampling_frequency,
-            signal_frequency: self.signal_frequency,
-            offset: self.offset,
+            key: self.memo.key.clone(),
+            reconstruction: self.reconstruction,
             fft_len: fft_output.len(),
             reconstructed_signal_output: result.clone(),
         });
@@ -413,81 +1091,144 @@ impl AliasApp {
         &mut self,
         horizontal_pixels: u32,
         fft_output: &Vec<Complex<f32>>,
-    ) -> Vec<(f32, f32)> {
+    ) -> Vec<(f32, Complex<f32>)> {
         let n_recon_points = horizontal_pixels as usize;
+        if self.reconstruction.method != ReconstructionMethod::Fft
+            || fft_output.len() > MAX_FFT_RECONSTRUCTION_SIZE
+        {
+            return self._calculate_interpolated_signal(n_recon_points);
+        }
+
         let mut recon_signal = Vec::with_capacity(n_recon_points);
         let fft_size = fft_output.len();
         let freq_resolution = self.sampling_frequency / fft_size as f32;
+        let sampling_offset = self.sampling.offset(self.sampling_frequency);
 
         assert!(fft_output.len() % 2 == 0);
 
         for i in 0..n_recon_points {
-            let x = i as f32 / n_recon_points as f32 * 2.0 * PI;
+            let t = i as f32 / n_recon_points as f32 * self.window_duration;
 
             let y = {
-                // Reconstruct from FFT data (Inverse Fourier transform simplified)
-                let mut y_value = 0.0;
+                // Reconstruct from FFT data (inverse Fourier transform at an arbitrary t):
+                // y(t) = 1/N * sum_k [ X_k * exp(j*2π*f_k*t) ]
+                // For a real signal the bins come in conjugate pairs and the imaginary part cancels.
+                let mut y_value = Complex::new(0.0, 0.0);
 
-                // Use precise frequency reconstruction formula:
-                // y(t) = sum_k [ A_k * cos(2π*f_k*t + φ_k) ]
-                for k in 0..fft_size {
-                    // the FFT is mirrored
+                for (k, bin) in fft_output.iter().enumerate() {
+                    // the upper half of the bins are the negative frequencies
                     let freq = if k <= fft_size / 2 {
                         k as f32 * freq_resolution
                     } else {
                         (k as i32 - fft_size as i32) as f32 * freq_resolution
                     };
 
-                    // Get amplitude and phase from complex FFT output
-                    let amplitude: f32 = fft_output[k].norm();
-                    let phase: f32 = fft_output[k].arg();
-
-                    // Add this frequency component's contribution at time x
-                    if freq > 0.0 {
-                        y_value += amplitude * (freq * x + phase).cos() / (fft_size as f32);
-                    } else {
-                        y_value += amplitude * (-1.0 * freq * x - phase).cos() / (fft_size as f32);
-                    }
+                    // Add this frequency component's contribution at time t,
+                    // sample 0 was taken at the phase offset of the sampling clock
+                    let omega = 2.0 * PI * freq;
+                    y_value += bin * Complex::from_polar(1.0, omega * (t - sampling_offset));
                 }
 
-                y_value
+                y_value / fft_size as f32
             };
 
-            recon_signal.push((x, y));
+            recon_signal.push((t, y));
         }
 
         recon_signal
     }
+
+    /// Rebuilds the signal by interpolating the samples in the time domain,
+    /// see [`Reconstruction::reconstruct`].
+    fn _calculate_interpolated_signal(&self, n_recon_points: usize) -> Vec<(f32, Complex<f32>)> {
+        // only reached with the FFT method when there are too many bins
+        let reconstruction = match self.reconstruction.method {
+            ReconstructionMethod::Fft => Reconstruction {
+                method: ReconstructionMethod::Sinc,
+                ..self.reconstruction
+            },
+            _ => self.reconstruction,
+        };
+        // samples past the window still contribute to the sinc near its right edge
+        let n_samples = self.window_sample_count() + self.reconstruction.sinc_taps as usize + 1;
+        // like a DAC, the samples are output at the nominal instants of the sampling clock,
+        // so that jitter shows up as an error of the reconstruction
+        let mut samples: Vec<(f32, Complex<f32>)> = self
+            .sampling
+            .sample_times(n_samples, self.sampling_frequency, self.window_duration)
+            .into_iter()
+            .zip(self.sample_signal_complex(n_samples))
+            .collect();
+        // user placed samples can be dragged past each other
+        samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let (sample_times, values): (Vec<f32>, Vec<Complex<f32>>) = samples.into_iter().unzip();
+
+        let recon_times: Vec<f32> = (0..n_recon_points)
+            .map(|i| i as f32 / n_recon_points as f32 * self.window_duration)
+            .collect();
+        let recon_values = reconstruction.reconstruct(
+            &sample_times,
+            &values,
+            self.sampling_frequency,
+            &recon_times,
+        );
+        recon_times.into_iter().zip(recon_values).collect()
+    }
 }
 
 impl AliasApp {
     fn calculate_signal(&mut self, horizontal_pixels: u32) -> Vec<(f32, f32)> {
         if let Some(ref memo) = self.memo.signal {
             if memo.horizontal_pixels == horizontal_pixels
-                && memo.signal_frequency == self.signal_frequency
-                && memo.offset == self.offset
+                && memo.window_duration == self.window_duration
+                && memo.signal == self.signal
             {
                 return memo.signal_output.clone();
             }
         }
 
-        let result = self._calculate_signal(horizontal_pixels);
+        let result = self._calculate_signal(horizontal_pixels, Signal::value);
         self.memo.signal = Some(SignalMemoization {
             horizontal_pixels,
-            signal_frequency: self.signal_frequency,
-            offset: self.offset,
+            signal: self.signal.clone(),
+            window_duration: self.window_duration,
+            signal_output: result.clone(),
+        });
+        result
+    }
+
+    /// The quadrature part of a complex signal, see [`Self::calculate_signal`].
+    fn calculate_quadrature_signal(&mut self, horizontal_pixels: u32) -> Vec<(f32, f32)> {
+        if let Some(ref memo) = self.memo.quadrature_signal {
+            if memo.horizontal_pixels == horizontal_pixels
+                && memo.window_duration == self.window_duration
+                && memo.signal == self.signal
+            {
+                return memo.signal_output.clone();
+            }
+        }
+
+        let result = self._calculate_signal(horizontal_pixels, Signal::quadrature_value);
+        self.memo.quadrature_signal = Some(SignalMemoization {
+            horizontal_pixels,
+            signal: self.signal.clone(),
+            window_duration: self.window_duration,
             signal_output: result.clone(),
         });
         result
     }
 
-    fn _calculate_signal(&mut self, horizontal_pixels: u32) -> Vec<(f32, f32)> {
+    fn _calculate_signal(
+        &mut self,
+        horizontal_pixels: u32,
+        value: fn(&Signal, f32) -> f32,
+    ) -> Vec<(f32, f32)> {
         let n_signal_points = horizontal_pixels;
         let signal: Vec<(f32, f32)> = (0..n_signal_points)
             .map(|i| {
-                let x = i as f32 / n_signal_points as f32 * 2.0 * PI;
-                let y = (self.signal_frequency * x + self.offset * PI).sin();
-                (x, y)
+                let t = i as f32 / n_signal_points as f32 * self.window_duration;
+                let y = value(&self.signal, t);
+                (t, y)
             })
             .collect();
         signal
@@ -497,78 +1238,497 @@ impl AliasApp {
 impl AliasApp {
     fn calculate_sample_points(&mut self) -> Vec<(f32, f32)> {
         if let Some(ref memo) = self.memo.sample_points {
-            if memo.sampling_frequency == self.sampling_frequency
-                && memo.signal_frequency == self.signal_frequency
-                && memo.offset == self.offset
-            {
+            if memo.key == self.memo.key {
                 return memo.sample_points_output.clone();
             }
         }
 
-        let result = self._calculate_sample_points();
+        let result = self._calculate_sample_points(self.sample_signal(self.window_sample_count()));
         self.memo.sample_points = Some(SamplePointsMemoization {
-            sampling_frequency: self.sampling_frequency,
-            signal_frequency: self.signal_frequency,
-            offset: self.offset,
+            key: self.memo.key.clone(),
             sample_points_output: result.clone(),
         });
         result
     }
 
-    fn _calculate_sample_points(&mut self) -> Vec<(f32, f32)> {
-        let n_sample_points = self.sampling_frequency as u32 + 1;
-        let sample_points: Vec<(f32, f32)> = (0..n_sample_points) // More potential points
-            .map(|i| {
-                // Fixed calculation: correctly calculate sample points based on sampling frequency
-                // Time per sample = 1.0 / sampling_frequency (in seconds)
-                // Convert to our x-scale which is in [0, 2π]
-                let sample_x = i as f32 * (2.0 * PI / self.sampling_frequency);
-                let sample_y = (self.signal_frequency * sample_x + self.offset * PI).sin();
-                (sample_x, sample_y)
-            })
+    /// The quadrature part of the samples of a complex signal, see [`Self::calculate_sample_points`].
+    fn calculate_quadrature_sample_points(&mut self) -> Vec<(f32, f32)> {
+        if let Some(ref memo) = self.memo.quadrature_sample_points {
+            if memo.key == self.memo.key {
+                return memo.sample_points_output.clone();
+            }
+        }
+
+        let result =
+            self._calculate_sample_points(self.sample_quadrature(self.window_sample_count()));
+        self.memo.quadrature_sample_points = Some(SamplePointsMemoization {
+            key: self.memo.key.clone(),
+            sample_points_output: result.clone(),
+        });
+        result
+    }
+
+    fn _calculate_sample_points(&mut self, samples: Vec<f32>) -> Vec<(f32, f32)> {
+        // plot each sample at the instant it was actually taken
+        let sample_times = self.sample_times(samples.len());
+        let sample_points: Vec<(f32, f32)> = sample_times
+            .into_iter()
+            .zip(samples)
+            .filter(|(t, _)| (0.0..=self.window_duration).contains(t))
             .collect();
         sample_points
     }
+
+    /// How many samples are taken within the displayed window.
+    fn window_sample_count(&self) -> usize {
+        match self.sampling.mode {
+            SamplingMode::UserPlaced => self.sampling.user_times.len(),
+            _ => (self.window_duration * self.sampling_frequency) as usize + 1,
+        }
+    }
+
+    /// The instants (in seconds) the first `n_samples` samples are taken at: on average every
+    /// 1.0 / sampling_frequency, following the sampling mode and the jitter of the sampling clock.
+    fn sample_times(&self, n_samples: usize) -> Vec<f32> {
+        let mut sample_times =
+            self.sampling
+                .sample_times(n_samples, self.sampling_frequency, self.window_duration);
+        self.jitter.apply(&mut sample_times);
+        sample_times
+    }
+
+    /// Samples the first `n_samples` values of the signal at `sampling_frequency`,
+    /// including jitter, noise and quantization.
+    ///
+    /// Both the sample points and the FFT input are generated here, so that they agree sample by sample.
+    fn sample_signal(&self, n_samples: usize) -> Vec<f32> {
+        let mut samples = self.sample_analog(n_samples);
+        self.adc.apply(&mut samples);
+        samples
+    }
+
+    /// The samples as they enter the ADC, including jitter and noise.
+    fn sample_analog(&self, n_samples: usize) -> Vec<f32> {
+        let mut samples = self.filtered_values(&self.sample_times(n_samples), Signal::value);
+        self.noise
+            .apply(&mut samples, self.signal.ac_power(self.window_duration));
+        samples
+    }
+
+    /// The signal at the instants `times` after the anti-aliasing filter.
+    fn filtered_values(&self, times: &[f32], value: fn(&Signal, f32) -> f32) -> Vec<f32> {
+        self.filter.apply(
+            |t| value(&self.signal, t),
+            times,
+            self.sampling_frequency,
+            self.signal.max_frequency(),
+        )
+    }
+
+    /// Samples the quadrature part of a complex signal like [`Self::sample_signal`].
+    ///
+    /// The noise and dither are independent of the in-phase ones, but have the same power,
+    /// so that the SNR holds for the complex signal as a whole.
+    fn sample_quadrature(&self, n_samples: usize) -> Vec<f32> {
+        let mut samples =
+            self.filtered_values(&self.sample_times(n_samples), Signal::quadrature_value);
+        let noise = Noise {
+            seed: self.noise.seed.wrapping_add(1),
+            ..self.noise
+        };
+        noise.apply(&mut samples, self.signal.ac_power(self.window_duration));
+        let adc = Adc {
+            seed: self.adc.seed.wrapping_add(1),
+            ..self.adc
+        };
+        adc.apply(&mut samples);
+        samples
+    }
+
+    /// The samples as complex numbers, the imaginary part is only sampled for complex signals.
+    fn sample_signal_complex(&self, n_samples: usize) -> Vec<Complex<f32>> {
+        let in_phase = self.sample_signal(n_samples);
+        if !self.signal.is_complex() {
+            return in_phase.into_iter().map(|i| Complex::new(i, 0.0)).collect();
+        }
+        let quadrature = self.sample_quadrature(n_samples);
+        in_phase
+            .into_iter()
+            .zip(quadrature)
+            .map(|(i, q)| Complex::new(i, q))
+            .collect()
+    }
+
+    /// Whether `frequency` lies outside of the band the sampler can represent unambiguously.
+    fn is_aliased(&self, frequency: f32) -> bool {
+        let nyquist_freq = self.sampling_frequency / 2.0;
+        if self.signal.is_complex() {
+            frequency < -nyquist_freq || frequency >= nyquist_freq
+        } else {
+            frequency >= nyquist_freq
+        }
+    }
+
+    /// Where `frequency` shows up after sampling: real signals fold into the first Nyquist zone,
+    /// complex signals wrap around into -fs/2..fs/2.
+    fn apparent_frequency(&self, frequency: f32) -> f32 {
+        if self.signal.is_complex() {
+            wrap_frequency(frequency, self.sampling_frequency)
+        } else {
+            alias_frequency(frequency, self.sampling_frequency)
+        }
+    }
 }
 
 impl AliasApp {
+    /// The upper end of the sampling frequency slider.
+    fn max_sampling_frequency(&self) -> f32 {
+        match (&self.signal.kind, &self.signal.wav.wav) {
+            // WAV files need rates up to their own sample rate
+            (SignalKind::Wav, Some(wav)) => (wav.sample_rate as f32).max(20.0),
+            // reach past 2 * the highest frequency, to compare undersampling to Nyquist sampling
+            (SignalKind::Bandpass, _) => (2.5 * self.signal.bandpass.band().1).max(20.0),
+            _ => 20.0,
+        }
+    }
+
     fn render_sliders(&mut self, ui: &mut egui::Ui) {
         ui.heading("Aliasing Demonstration");
         ui.add_space(10.0);
 
         ui.horizontal(|ui| {
-            ui.label("Signal Frequency:");
+            ui.label("Signal Type:");
+            egui::ComboBox::from_id_salt("signal_kind")
+                .selected_text(self.signal.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in SignalKind::ALL {
+                        ui.selectable_value(&mut self.signal.kind, kind, kind.name());
+                    }
+                });
+        });
+
+        match self.signal.kind {
+            SignalKind::Components => self.render_component_sliders(ui),
+            SignalKind::Chirp => self.render_chirp_sliders(ui),
+            SignalKind::Modulated => self.render_modulation_sliders(ui),
+            SignalKind::Wav => self.render_wav_sliders(ui),
+            SignalKind::Expression => self.render_expression_input(ui),
+            SignalKind::Iq => self.render_iq_sliders(ui),
+            SignalKind::Bandpass => self.render_bandpass_sliders(ui),
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Signal Amplitude:");
             ui.spacing_mut().slider_width = ui.available_width() - 100.0;
             ui.add(
-                egui::Slider::new(&mut self.signal_frequency, 0.1..=10.0)
-                    .text("Hz")
+                egui::Slider::new(&mut self.signal.amplitude, 0.0..=5.0)
                     .fixed_decimals(2)
                     .step_by(0.01),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Sampling Frequency:");
+            ui.label("DC Offset:");
             ui.spacing_mut().slider_width = ui.available_width() - 100.0;
             ui.add(
-                egui::Slider::new(&mut self.sampling_frequency, 0.1..=20.0)
-                    .text("Hz")
+                egui::Slider::new(&mut self.signal.dc_offset, -2.0..=2.0)
                     .fixed_decimals(2)
                     .step_by(0.01),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Phase shift:");
+            ui.checkbox(&mut self.filter.enabled, "Anti-Aliasing Filter:");
+            ui.add_enabled_ui(self.filter.enabled, |ui| {
+                egui::ComboBox::from_id_salt("filter_kind")
+                    .selected_text(self.filter.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in FilterKind::ALL {
+                            ui.selectable_value(&mut self.filter.kind, kind, kind.name());
+                        }
+                    });
+                ui.label("Order:");
+                ui.add(egui::Slider::new(&mut self.filter.order, 1..=8));
+                if self.filter.kind == FilterKind::Chebyshev {
+                    ui.label("Ripple:");
+                    ui.add(
+                        egui::Slider::new(&mut self.filter.ripple_db, 0.1..=3.0)
+                            .text("dB")
+                            .fixed_decimals(1),
+                    );
+                }
+                ui.label("Cutoff:");
+                let max_cutoff = self.max_sampling_frequency();
+                ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+                ui.add(
+                    egui::Slider::new(&mut self.filter.cutoff, 0.1..=max_cutoff)
+                        .logarithmic(true)
+                        .text("Hz")
+                        .fixed_decimals(2),
+                );
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Sampling Frequency:");
+            let max_sampling_frequency = self.max_sampling_frequency();
             ui.spacing_mut().slider_width = ui.available_width() - 100.0;
             ui.add(
-                egui::Slider::new(&mut self.offset, 0.0..=2.0)
-                    .text("π rad")
+                egui::Slider::new(&mut self.sampling_frequency, 0.1..=max_sampling_frequency)
+                    .logarithmic(max_sampling_frequency > 20.0)
+                    .text("Hz")
                     .fixed_decimals(2)
                     .step_by(0.01),
             );
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Window Duration:");
+            ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+            if ui
+                .add(
+                    egui::Slider::new(&mut self.window_duration, 0.1..=10.0)
+                        .logarithmic(true)
+                        .text("s")
+                        .fixed_decimals(2),
+                )
+                .changed()
+            {
+                // user placed samples repeat every window, drop the ones that no longer fit
+                let window_duration = self.window_duration;
+                self.sampling.user_times.retain(|t| *t < window_duration);
+                if self.sampling.user_times.is_empty()
+                    && self.sampling.mode == SamplingMode::UserPlaced
+                {
+                    self.sampling
+                        .reset_user_times(self.sampling_frequency, self.window_duration);
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Sampling Instants:");
+            let previous_mode = self.sampling.mode;
+            egui::ComboBox::from_id_salt("sampling_mode")
+                .selected_text(self.sampling.mode.name())
+                .show_ui(ui, |ui| {
+                    for mode in SamplingMode::ALL {
+                        ui.selectable_value(&mut self.sampling.mode, mode, mode.name());
+                    }
+                });
+            match self.sampling.mode {
+                SamplingMode::Uniform => {}
+                SamplingMode::Random | SamplingMode::AdditiveRandom => {
+                    if self.sampling.mode == SamplingMode::AdditiveRandom {
+                        ui.label("Spread:");
+                        ui.add(
+                            egui::Slider::new(&mut self.sampling.spread, 0.0..=1.0)
+                                .text("Ts")
+                                .fixed_decimals(2)
+                                .step_by(0.01),
+                        );
+                    }
+                    ui.label("Seed:");
+                    ui.add(egui::DragValue::new(&mut self.sampling.seed));
+                }
+                SamplingMode::UserPlaced => {
+                    // start out from the uniform grid
+                    if (previous_mode != SamplingMode::UserPlaced
+                        && self.sampling.user_times.is_empty())
+                        || ui.small_button("reset").clicked()
+                    {
+                        self.sampling
+                            .reset_user_times(self.sampling_frequency, self.window_duration);
+                    }
+                    ui.label(
+                        "drag the samples in the sample points plot, click to add one, \
+                         right-click to remove one",
+                    );
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Sampling Phase:");
+            // user placed samples are positioned directly
+            ui.add_enabled_ui(self.sampling.mode != SamplingMode::UserPlaced, |ui| {
+                let period = 1.0 / self.sampling_frequency;
+                let mut in_seconds = self.sampling.phase_in_seconds;
+                if ui.checkbox(&mut in_seconds, "in seconds").changed() {
+                    // only the unit changes, not the delay
+                    self.sampling.phase = if in_seconds {
+                        self.sampling.phase * period
+                    } else {
+                        self.sampling.phase / period
+                    };
+                    self.sampling.phase_in_seconds = in_seconds;
+                }
+                ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+                if self.sampling.phase_in_seconds {
+                    ui.add(
+                        egui::Slider::new(&mut self.sampling.phase, 0.0..=period)
+                            .text("s")
+                            .fixed_decimals(4),
+                    );
+                } else {
+                    ui.add(
+                        egui::Slider::new(&mut self.sampling.phase, 0.0..=1.0)
+                            .text("Ts")
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.noise.enabled, "Noise:");
+            ui.add_enabled_ui(self.noise.enabled, |ui| {
+                egui::ComboBox::from_id_salt("noise_kind")
+                    .selected_text(self.noise.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in NoiseKind::ALL {
+                            ui.selectable_value(&mut self.noise.kind, kind, kind.name());
+                        }
+                    });
+
+                ui.label("SNR:");
+                ui.add(
+                    egui::Slider::new(&mut self.noise.snr_db, -10.0..=60.0)
+                        .text("dB")
+                        .fixed_decimals(1)
+                        .step_by(0.1),
+                );
+
+                ui.label("Seed:");
+                ui.add(egui::DragValue::new(&mut self.noise.seed));
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.jitter.enabled, "Clock Jitter:");
+            ui.add_enabled_ui(self.jitter.enabled, |ui| {
+                egui::ComboBox::from_id_salt("jitter_kind")
+                    .selected_text(self.jitter.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in JitterKind::ALL {
+                            ui.selectable_value(&mut self.jitter.kind, kind, kind.name());
+                        }
+                    });
+                ui.label("RMS:");
+                ui.add(
+                    egui::Slider::new(&mut self.jitter.rms, 0.000001..=0.01)
+                        .logarithmic(true)
+                        .text("s")
+                        .fixed_decimals(6),
+                );
+                if self.jitter.kind == JitterKind::Sinusoidal {
+                    ui.label("Frequency:");
+                    ui.add(
+                        egui::Slider::new(&mut self.jitter.frequency, 0.1..=10.0)
+                            .text("Hz")
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
+                } else {
+                    ui.label("Seed:");
+                    ui.add(egui::DragValue::new(&mut self.jitter.seed));
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.adc.enabled, "ADC:");
+            ui.add_enabled_ui(self.adc.enabled, |ui| {
+                ui.add(egui::Slider::new(&mut self.adc.bits, 1..=24).text("bits"));
+                ui.label("Full scale: ±");
+                ui.add(
+                    egui::Slider::new(&mut self.adc.full_scale, 0.1..=5.0)
+                        .fixed_decimals(2)
+                        .step_by(0.01),
+                );
+                egui::ComboBox::from_id_salt("adc_rounding")
+                    .selected_text(self.adc.rounding.name())
+                    .show_ui(ui, |ui| {
+                        for rounding in Rounding::ALL {
+                            ui.selectable_value(&mut self.adc.rounding, rounding, rounding.name());
+                        }
+                    });
+                egui::ComboBox::from_id_salt("adc_dither")
+                    .selected_text(self.adc.dither.name())
+                    .show_ui(ui, |ui| {
+                        for dither in Dither::ALL {
+                            ui.selectable_value(&mut self.adc.dither, dither, dither.name());
+                        }
+                    });
+                if self.adc.dither != Dither::None {
+                    ui.label("Seed:");
+                    ui.add(egui::DragValue::new(&mut self.adc.seed));
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Reconstruction:");
+            egui::ComboBox::from_id_salt("reconstruction_method")
+                .selected_text(self.reconstruction.method.name())
+                .show_ui(ui, |ui| {
+                    for method in ReconstructionMethod::ALL {
+                        ui.selectable_value(&mut self.reconstruction.method, method, method.name());
+                    }
+                });
+            match self.reconstruction.method {
+                ReconstructionMethod::Sinc => {
+                    ui.label("Truncation: ±");
+                    ui.add(
+                        egui::Slider::new(&mut self.reconstruction.sinc_taps, 1..=64)
+                            .logarithmic(true)
+                            .text("samples"),
+                    );
+                }
+                ReconstructionMethod::ZeroOrderHold => {
+                    // the hold has the frequency response sinc(f / fs), -3.9 dB at Nyquist
+                    ui.label("Droop at Nyquist: -3.9 dB, delay: ½ sample");
+                }
+                _ => {}
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.multirate.enabled, "Rate Change:");
+            ui.add_enabled_ui(self.multirate.enabled, |ui| {
+                ui.label("↑L");
+                ui.add(egui::DragValue::new(&mut self.multirate.up).range(1..=16));
+                ui.label("↓M");
+                ui.add(egui::DragValue::new(&mut self.multirate.down).range(1..=16));
+                egui::ComboBox::from_id_salt("resampling_filter")
+                    .selected_text(self.multirate.filter.name())
+                    .show_ui(ui, |ui| {
+                        for filter in ResamplingFilter::ALL {
+                            ui.selectable_value(&mut self.multirate.filter, filter, filter.name());
+                        }
+                    });
+                match self.multirate.filter {
+                    ResamplingFilter::None => {}
+                    ResamplingFilter::WindowedSinc => {
+                        ui.label("Zero crossings:");
+                        ui.add(
+                            egui::DragValue::new(&mut self.multirate.sinc_zero_crossings)
+                                .range(1..=32),
+                        );
+                    }
+                    ResamplingFilter::Cic => {
+                        ui.label("Stages:");
+                        ui.add(egui::DragValue::new(&mut self.multirate.cic_stages).range(1..=6));
+                    }
+                }
+                ui.label(format!(
+                    "fs' = {:.2} Hz",
+                    self.multirate.output_rate(self.sampling_frequency)
+                ));
+            });
+        });
+
         ui.horizontal(|ui| {
             ui.label("FFT Size:");
             let mut is_checked = self.fft_size == FFTSize::Auto;
@@ -594,543 +1754,2753 @@ impl AliasApp {
                     }
                 }
             }
+
+            ui.label("Zero-padding:");
+            egui::ComboBox::from_id_salt("zero_padding")
+                .selected_text(format!("×{}", self.zero_padding.factor))
+                .show_ui(ui, |ui| {
+                    for factor in [1, 2, 4, 8, 16] {
+                        ui.selectable_value(
+                            &mut self.zero_padding.factor,
+                            factor,
+                            format!("×{factor}"),
+                        );
+                    }
+                });
+            if self.zero_padding.factor > 1 {
+                let mut centered = self.zero_padding.placement == PaddingPlacement::Centered;
+                if ui.checkbox(&mut centered, "centered").changed() {
+                    self.zero_padding.placement = if centered {
+                        PaddingPlacement::Centered
+                    } else {
+                        PaddingPlacement::End
+                    };
+                }
+            }
+
+            ui.label("Window:");
+            egui::ComboBox::from_id_salt("fft_window")
+                .selected_text(self.fft_window.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in WindowKind::ALL {
+                        ui.selectable_value(&mut self.fft_window.kind, kind, kind.name());
+                    }
+                });
+            match self.fft_window.kind {
+                WindowKind::Kaiser => {
+                    ui.label("β:");
+                    ui.add(
+                        egui::Slider::new(&mut self.fft_window.kaiser_beta, 0.0..=20.0)
+                            .fixed_decimals(1),
+                    );
+                }
+                WindowKind::Gaussian => {
+                    ui.label("σ:");
+                    ui.add(
+                        egui::Slider::new(&mut self.fft_window.gaussian_sigma, 0.1..=1.0)
+                            .fixed_decimals(2),
+                    );
+                }
+                _ => {}
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("FFT Scale:");
+            egui::ComboBox::from_id_salt("magnitude_scale")
+                .selected_text(self.magnitude_scale.name())
+                .show_ui(ui, |ui| {
+                    for scale in MagnitudeScale::ALL {
+                        ui.selectable_value(&mut self.magnitude_scale, scale, scale.name());
+                    }
+                });
+            if self.magnitude_scale != MagnitudeScale::Linear {
+                ui.label("Floor:");
+                ui.add(
+                    egui::Slider::new(&mut self.db_floor, -200.0..=-20.0)
+                        .text("dB")
+                        .fixed_decimals(0),
+                );
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.phase_plot.enabled, "Phase plot:");
+            ui.add_enabled_ui(self.phase_plot.enabled, |ui| {
+                ui.label("Threshold:");
+                ui.add(
+                    egui::Slider::new(&mut self.phase_plot.threshold_db, -120.0..=0.0)
+                        .text("dB below the strongest bin")
+                        .fixed_decimals(0),
+                );
+                ui.checkbox(&mut self.phase_plot.unwrap, "unwrap");
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.spectrogram.enabled, "Spectrogram:");
+            ui.add_enabled_ui(self.spectrogram.enabled, |ui| {
+                ui.label("Segment:");
+                egui::ComboBox::from_id_salt("spectrogram_segment")
+                    .selected_text(format!("{} samples", self.spectrogram.segment_length))
+                    .show_ui(ui, |ui| {
+                        for length in [8, 16, 32, 64, 128, 256, 512, 1024] {
+                            ui.selectable_value(
+                                &mut self.spectrogram.segment_length,
+                                length,
+                                format!("{length} samples"),
+                            );
+                        }
+                    });
+                ui.label("Hop:");
+                ui.add(
+                    egui::DragValue::new(&mut self.spectrogram.hop)
+                        .range(1..=self.spectrogram.segment_length),
+                );
+                ui.label("Window:");
+                egui::ComboBox::from_id_salt("spectrogram_window")
+                    .selected_text(self.spectrogram.window.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in WindowKind::ALL {
+                            ui.selectable_value(
+                                &mut self.spectrogram.window.kind,
+                                kind,
+                                kind.name(),
+                            );
+                        }
+                    });
+                ui.label("Range:");
+                ui.add(
+                    egui::Slider::new(&mut self.spectrogram.dynamic_range_db, 20.0..=120.0)
+                        .text("dB")
+                        .fixed_decimals(0),
+                );
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.replica_plot.enabled, "Spectral replicas:");
+            ui.add_enabled_ui(self.replica_plot.enabled, |ui| {
+                ui.label("Periods:");
+                ui.add(egui::DragValue::new(&mut self.replica_plot.periods).range(1..=8))
+                    .on_hover_text("how many multiples of fs are shown on either side of 0 Hz");
+            });
         });
     }
 }
 
 impl AliasApp {
-    fn render_signal_graph(
-        &mut self,
-        ui: &mut egui::Ui,
-        signal: &Vec<(f32, f32)>,
-        sample_points: &Vec<(f32, f32)>,
-        plot_height: f32,
-        plot_width: f32,
-        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
-    ) {
-        ui.colored_label(
-            Color32::YELLOW,
-            format!("Signal ({}Hz)", self.signal_frequency),
-        );
-        let response1 = ui.allocate_rect(
-            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
-            egui::Sense::hover(),
-        );
+    fn render_component_sliders(&mut self, ui: &mut egui::Ui) {
+        let can_remove = self.signal.components.len() > 1;
+        let mut remove_index = None;
+        for (i, component) in self.signal.components.iter_mut().enumerate() {
+            ui.push_id(i, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Signal Frequency {}:", i + 1));
+                    egui::ComboBox::from_id_salt("waveform")
+                        .selected_text(component.waveform.name())
+                        .show_ui(ui, |ui| {
+                            for waveform in Waveform::ALL {
+                                ui.selectable_value(
+                                    &mut component.waveform,
+                                    waveform,
+                                    waveform.name(),
+                                );
+                            }
+                        });
+                    ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+                    ui.add(
+                        egui::Slider::new(&mut component.frequency, 0.1..=10.0)
+                            .text("Hz")
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
+                });
 
-        let rect = response1.rect.intersect(ui.clip_rect());
-        let painter = ui.painter();
+                ui.horizontal(|ui| {
+                    ui.label("Amplitude:");
+                    ui.add(
+                        egui::Slider::new(&mut component.amplitude, 0.0..=1.0)
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
 
-        // Draw signal
-        for i in 0..signal.len() - 1 {
-            let (x1, y1) = signal[i];
-            let (x2, y2) = signal[i + 1];
-            painter.line_segment(
-                [
-                    rect.left_top()
-                        + vec2(
-                            x1 / (2.0 * PI) * rect.width(),
-                            rect.height() / 2.0 - y1 * (rect.height() / 2.0),
-                        ),
-                    rect.left_top()
-                        + vec2(
-                            x2 / (2.0 * PI) * rect.width(),
-                            rect.height() / 2.0 - y2 * (rect.height() / 2.0),
-                        ),
-                ],
-                Stroke::new(2.0, Color32::GREEN),
-            );
-        }
+                    ui.label("Phase shift:");
+                    ui.add(
+                        egui::Slider::new(&mut component.phase, 0.0..=2.0)
+                            .text("π rad")
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
 
-        // Draw vertical lines at sample points
-        for (x, _) in sample_points {
-            let x_pos = rect.left_top().x + *x / (2.0 * PI) * rect.width();
-            painter.line_segment(
-                [
-                    egui::Pos2::new(x_pos, rect.top()),
-                    egui::Pos2::new(x_pos, rect.bottom()),
-                ],
-                Stroke::new(1.0, Color32::from_rgba_premultiplied(255, 0, 0, 100)),
-            );
+                    if component.waveform == Waveform::Pulse {
+                        ui.label("Duty cycle:");
+                        ui.add(
+                            egui::Slider::new(&mut component.duty_cycle, 0.01..=0.99)
+                                .fixed_decimals(2)
+                                .step_by(0.01),
+                        );
+                    }
+
+                    if component.waveform != Waveform::Sine {
+                        ui.checkbox(&mut component.band_limited, "band-limited");
+                    }
+
+                    if can_remove && ui.small_button("remove").clicked() {
+                        remove_index = Some(i);
+                    }
+                });
+            });
+        }
+        if let Some(i) = remove_index {
+            self.signal.components.remove(i);
         }
 
-        // Draw sample points
-        for (x, y) in sample_points {
-            painter.circle_filled(
-                rect.left_top()
-                    + vec2(
-                        *x / (2.0 * PI) * rect.width(),
-                        rect.height() / 2.0 - y * (rect.height() / 2.0),
-                    ),
-                4.0,
-                Color32::RED,
-            );
+        if ui.small_button("add component").clicked() {
+            self.signal.components.push(SignalComponent {
+                amplitude: 0.5,
+                ..Default::default()
+            });
         }
+    }
 
-        // draw_axis_labels(painter, rect, "Time", "Amplitude");
+    fn render_iq_sliders(&mut self, ui: &mut egui::Ui) {
+        let can_remove = self.signal.iq_tones.len() > 1;
+        let mut remove_index = None;
+        for (i, tone) in self.signal.iq_tones.iter_mut().enumerate() {
+            ui.push_id(i, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Tone Frequency {}:", i + 1));
+                    ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+                    ui.add(
+                        egui::Slider::new(&mut tone.frequency, -10.0..=10.0)
+                            .text("Hz")
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
+                });
 
-        // Add legend
-        painter.rect_filled(
-            egui::Rect::from_min_max(
-                egui::Pos2::new(rect.right() - 120.0, rect.top() + 10.0),
-                egui::Pos2::new(rect.right() - 10.0, rect.top() + 50.0),
-            ),
-            3.0,
-            Color32::from_rgba_premultiplied(40, 40, 40, 200),
-        );
+                ui.horizontal(|ui| {
+                    ui.label("Amplitude:");
+                    ui.add(
+                        egui::Slider::new(&mut tone.amplitude, 0.0..=1.0)
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
 
-        painter.line_segment(
-            [
-                egui::Pos2::new(rect.right() - 110.0, rect.top() + 20.0),
-                egui::Pos2::new(rect.right() - 90.0, rect.top() + 20.0),
-            ],
-            Stroke::new(2.0, Color32::GREEN),
-        );
+                    ui.label("Phase shift:");
+                    ui.add(
+                        egui::Slider::new(&mut tone.phase, 0.0..=2.0)
+                            .text("π rad")
+                            .fixed_decimals(2)
+                            .step_by(0.01),
+                    );
+
+                    if can_remove && ui.small_button("remove").clicked() {
+                        remove_index = Some(i);
+                    }
+                });
+            });
+        }
+        if let Some(i) = remove_index {
+            self.signal.iq_tones.remove(i);
+        }
+
+        if ui.small_button("add tone").clicked() {
+            self.signal.iq_tones.push(IqTone {
+                amplitude: 0.5,
+                ..Default::default()
+            });
+        }
+    }
+
+    fn render_expression_input(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("f(t) =");
+            // the expression keeps its text even when it doesn't compile, so edit a copy
+            // and only recompile when it changed
+            let mut text = self.signal.expression.text().to_string();
+            let mut edit = egui::TextEdit::singleline(&mut text)
+                .desired_width(ui.available_width() - 50.0)
+                .font(egui::TextStyle::Monospace);
+            if self.signal.expression.error().is_some() {
+                edit = edit.text_color(Color32::RED);
+            }
+            if ui.add(edit).changed() {
+                self.signal.expression = Expression::new(&text);
+            }
+        });
+
+        match self.signal.expression.error() {
+            Some(error) => {
+                ui.colored_label(Color32::RED, format!("Invalid expression: {error}"));
+            }
+            None => {
+                ui.label(
+                    "t in seconds; + - * / ^, pi, e, sin cos tan exp ln log sqrt abs sign \
+                     floor ceil round fract sinc, min(a,b) max(a,b) pow(a,b) mod(a,b)",
+                );
+            }
+        }
+    }
+
+    fn render_wav_sliders(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                ui.label("WAV file:");
+                ui.text_edit_singleline(&mut self.wav_path);
+                if ui.small_button("load").clicked() {
+                    let path = std::path::PathBuf::from(&self.wav_path);
+                    self.load_wav_file(&path);
+                }
+                if ui.small_button("browse…").clicked() {
+                    self.file_browser.open_at(&self.wav_path);
+                }
+                if let Some(path) = self.file_browser.show(ui.ctx()) {
+                    self.wav_path = path.to_string_lossy().to_string();
+                    self.load_wav_file(&path);
+                }
+            }
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            ui.label("(or drop a .wav file onto the window)");
+        });
+
+        if let Some(error) = &self.wav_error {
+            ui.colored_label(Color32::RED, format!("Failed to load WAV file: {error}"));
+        }
+
+        if let Some(wav) = self.signal.wav.wav.clone() {
+            ui.horizontal(|ui| {
+                ui.label("Start:");
+                ui.spacing_mut().slider_width = ui.available_width() - 300.0;
+                ui.add(
+                    egui::Slider::new(
+                        &mut self.signal.wav.start,
+                        0.0..=(wav.duration() - self.window_duration).max(0.0),
+                    )
+                    .text("s")
+                    .fixed_decimals(3),
+                );
+                ui.label(format!(
+                    "{}: {} Hz, {:.2} s",
+                    self.signal.wav.name,
+                    wav.sample_rate,
+                    wav.duration()
+                ));
+            });
+        }
+    }
+
+    fn render_modulation_sliders(&mut self, ui: &mut egui::Ui) {
+        let modulation = &mut self.signal.modulation;
+
+        ui.horizontal(|ui| {
+            ui.label("Carrier Frequency:");
+            egui::ComboBox::from_id_salt("modulation_kind")
+                .selected_text(modulation.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in ModulationKind::ALL {
+                        ui.selectable_value(&mut modulation.kind, kind, kind.name());
+                    }
+                });
+            ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+            ui.add(
+                egui::Slider::new(&mut modulation.carrier_frequency, 0.1..=20.0)
+                    .text("Hz")
+                    .fixed_decimals(2)
+                    .step_by(0.01),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Modulation Frequency:");
+            ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+            ui.add(
+                egui::Slider::new(&mut modulation.modulation_frequency, 0.1..=5.0)
+                    .text("Hz")
+                    .fixed_decimals(2)
+                    .step_by(0.01),
+            );
+        });
+
+        ui.horizontal(|ui| match modulation.kind {
+            ModulationKind::Am => {
+                ui.label("Modulation Depth:");
+                ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+                ui.add(
+                    egui::Slider::new(&mut modulation.depth, 0.0..=1.0)
+                        .fixed_decimals(2)
+                        .step_by(0.01),
+                );
+            }
+            ModulationKind::Fm => {
+                ui.label("Frequency Deviation:");
+                ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+                ui.add(
+                    egui::Slider::new(&mut modulation.deviation, 0.0..=10.0)
+                        .text("Hz")
+                        .fixed_decimals(2)
+                        .step_by(0.01),
+                );
+            }
+        });
+    }
+
+    fn render_bandpass_sliders(&mut self, ui: &mut egui::Ui) {
+        let bandpass = &mut self.signal.bandpass;
+
+        ui.horizontal(|ui| {
+            ui.label("Center Frequency:");
+            ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+            ui.add(
+                egui::Slider::new(&mut bandpass.center_frequency, 0.5..=40.0)
+                    .text("Hz")
+                    .fixed_decimals(2)
+                    .step_by(0.01),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Bandwidth:");
+            ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+            ui.add(
+                egui::Slider::new(&mut bandpass.bandwidth, 0.1..=10.0)
+                    .text("Hz")
+                    .fixed_decimals(2)
+                    .step_by(0.01),
+            );
+        });
+
+        let (low, high) = bandpass.band();
+        match bandpass.nyquist_zone(self.sampling_frequency) {
+            Some(zone) => {
+                let (folded_low, folded_high) = alias_range(low, high, self.sampling_frequency)[0];
+                let inverted = bandpass.is_inverted(self.sampling_frequency);
+                ui.label(format!(
+                    "The band {low:.2} .. {high:.2} Hz lies in Nyquist zone {zone} and folds to \
+                     {folded_low:.2} .. {folded_high:.2} Hz{}",
+                    if inverted {
+                        ", with its spectrum inverted"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+            None => {
+                ui.colored_label(
+                    Color32::RED,
+                    format!(
+                        "The band {low:.2} .. {high:.2} Hz straddles a multiple of fs/2, \
+                         its folded halves overlap"
+                    ),
+                );
+            }
+        }
+
+        self.render_bandpass_chart(ui);
+    }
+
+    /// A chart of the sampling frequencies that keep the band within one Nyquist zone,
+    /// clicking it selects the sampling frequency.
+    fn render_bandpass_chart(&mut self, ui: &mut egui::Ui) {
+        let max_fs = self.max_sampling_frequency();
+        let ranges = self.signal.bandpass.valid_sampling_ranges();
+
+        ui.label("Valid sampling frequencies (click to select):");
+        let response = ui.allocate_rect(
+            egui::Rect::from_min_size(
+                ui.cursor().min,
+                egui::Vec2::new(ui.available_width() - 20.0, 40.0),
+            ),
+            egui::Sense::click(),
+        );
+        let rect = response.rect;
+        let painter = ui.painter();
+        let fs_to_x = |fs: f32| rect.left() + (fs / max_fs).min(1.0) * rect.width();
+
+        if let Some(pos) = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked())
+        {
+            self.sampling_frequency =
+                ((pos.x - rect.left()) / rect.width() * max_fs).clamp(0.1, max_fs);
+        }
+
+        // everything outside of the valid ranges folds the band onto itself
+        painter.rect_filled(rect, 0.0, Color32::from_rgba_premultiplied(60, 0, 0, 60));
+        for (zone, min_fs, max_fs) in &ranges {
+            let (x_min, x_max) = (fs_to_x(*min_fs), fs_to_x(*max_fs));
+            if x_min >= rect.right() {
+                continue;
+            }
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::Pos2::new(x_min, rect.top()),
+                    egui::Pos2::new(x_max.max(x_min + 1.0), rect.bottom() - 15.0),
+                ),
+                0.0,
+                Color32::from_rgba_premultiplied(0, 100, 0, 150),
+            );
+            if x_max - x_min > 30.0 {
+                painter.text(
+                    egui::Pos2::new((x_min + x_max) / 2.0, rect.top() + 12.0),
+                    egui::Align2::CENTER_CENTER,
+                    format!("zone {zone}"),
+                    egui::FontId::proportional(12.0),
+                    Color32::YELLOW,
+                );
+            }
+        }
+
+        // Draw sampling frequency ticks
+        let tick_step = tick_step(max_fs, 8);
+        for i_tick in 0..=(max_fs / tick_step) as u32 {
+            let fs = i_tick as f32 * tick_step;
+            painter.text(
+                egui::Pos2::new(fs_to_x(fs), rect.bottom() - 7.0),
+                egui::Align2::CENTER_CENTER,
+                format!("{fs} Hz"),
+                egui::FontId::proportional(10.0),
+                Color32::YELLOW,
+            );
+        }
+
+        // Mark the current sampling frequency
+        let valid = ranges
+            .iter()
+            .any(|(_, min_fs, max_fs)| (*min_fs..=*max_fs).contains(&self.sampling_frequency));
+        let x = fs_to_x(self.sampling_frequency);
+        painter.line_segment(
+            [
+                egui::Pos2::new(x, rect.top()),
+                egui::Pos2::new(x, rect.bottom() - 15.0),
+            ],
+            Stroke::new(2.0, if valid { Color32::GREEN } else { Color32::RED }),
+        );
+    }
+
+    fn render_chirp_sliders(&mut self, ui: &mut egui::Ui) {
+        let chirp = &mut self.signal.chirp;
+
+        ui.horizontal(|ui| {
+            ui.label("Start Frequency:");
+            egui::ComboBox::from_id_salt("chirp_sweep")
+                .selected_text(chirp.sweep.name())
+                .show_ui(ui, |ui| {
+                    for sweep in ChirpSweep::ALL {
+                        ui.selectable_value(&mut chirp.sweep, sweep, sweep.name());
+                    }
+                });
+            ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+            ui.add(
+                egui::Slider::new(&mut chirp.start_frequency, 0.1..=20.0)
+                    .text("Hz")
+                    .fixed_decimals(2)
+                    .step_by(0.01),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("End Frequency:");
+            ui.spacing_mut().slider_width = ui.available_width() - 100.0;
+            ui.add(
+                egui::Slider::new(&mut chirp.end_frequency, 0.1..=20.0)
+                    .text("Hz")
+                    .fixed_decimals(2)
+                    .step_by(0.01),
+            );
+        });
+    }
+}
+
+impl AliasApp {
+    fn render_signal_graph(
+        &mut self,
+        ui: &mut egui::Ui,
+        signal: &Vec<(f32, f32)>,
+        sample_points: &Vec<(f32, f32)>,
+        plot_height: f32,
+        plot_width: f32,
+        y_range: (f32, f32),
+        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
+    ) {
+        let description = match self.signal.kind {
+            SignalKind::Components => self
+                .signal
+                .components
+                .iter()
+                .map(|c| format!("{}Hz", c.frequency))
+                .collect::<Vec<_>>()
+                .join(" + "),
+            SignalKind::Chirp => format!(
+                "{} chirp {}Hz to {}Hz",
+                self.signal.chirp.sweep.name(),
+                self.signal.chirp.start_frequency,
+                self.signal.chirp.end_frequency
+            ),
+            SignalKind::Modulated => format!(
+                "{} carrier {}Hz, modulation {}Hz",
+                self.signal.modulation.kind.name(),
+                self.signal.modulation.carrier_frequency,
+                self.signal.modulation.modulation_frequency
+            ),
+            SignalKind::Wav => match &self.signal.wav.wav {
+                Some(_) => format!(
+                    "{} from {:.3}s",
+                    self.signal.wav.name, self.signal.wav.start
+                ),
+                None => "no WAV file loaded".to_string(),
+            },
+            SignalKind::Expression => self.signal.expression.text().to_string(),
+            SignalKind::Bandpass => format!(
+                "band {}Hz ± {}Hz",
+                self.signal.bandpass.center_frequency,
+                self.signal.bandpass.bandwidth / 2.0
+            ),
+            SignalKind::Iq => self
+                .signal
+                .iq_tones
+                .iter()
+                .map(|tone| format!("exp(j2π·{}Hz·t)", tone.frequency))
+                .collect::<Vec<_>>()
+                .join(" + "),
+        };
+        ui.colored_label(Color32::YELLOW, format!("Signal ({description})"));
+        let response1 = ui.allocate_rect(
+            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
+            egui::Sense::hover(),
+        );
+
+        let rect = response1.rect.intersect(ui.clip_rect());
+        let painter = ui.painter();
+
+        // Draw time ticks across the observed window
+        let time_step = tick_step(self.window_duration, 10);
+        for i_tick in 0..=(self.window_duration / time_step) as u32 {
+            let t = i_tick as f32 * time_step;
+            let x_pos = rect.left() + x_offset(rect, t, self.window_duration);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(x_pos, rect.bottom() - 5.0),
+                    egui::Pos2::new(x_pos, rect.bottom()),
+                ],
+                Stroke::new(1.0, Color32::GRAY),
+            );
+            painter.text(
+                egui::Pos2::new(x_pos + 2.0, rect.bottom() - 8.0),
+                egui::Align2::LEFT_BOTTOM,
+                format!("{} s", (t * 1000.0).round() / 1000.0),
+                egui::FontId::proportional(11.0),
+                Color32::GRAY,
+            );
+        }
+
+        // Draw signal
+        for i in 0..signal.len() - 1 {
+            let (x1, y1) = signal[i];
+            let (x2, y2) = signal[i + 1];
+            painter.line_segment(
+                [
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x1, self.window_duration),
+                            y_offset(rect, y1, y_range),
+                        ),
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x2, self.window_duration),
+                            y_offset(rect, y2, y_range),
+                        ),
+                ],
+                Stroke::new(2.0, Color32::GREEN),
+            );
+        }
+
+        // Draw the quadrature part of a complex signal
+        if self.signal.is_complex() {
+            let quadrature_signal = self.calculate_quadrature_signal(signal.len() as u32);
+            for i in 0..quadrature_signal.len() - 1 {
+                let (x1, y1) = quadrature_signal[i];
+                let (x2, y2) = quadrature_signal[i + 1];
+                painter.line_segment(
+                    [
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x1, self.window_duration),
+                                y_offset(rect, y1, y_range),
+                            ),
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x2, self.window_duration),
+                                y_offset(rect, y2, y_range),
+                            ),
+                    ],
+                    Stroke::new(2.0, Color32::from_rgb(0, 170, 255)),
+                );
+            }
+            for (x, y) in self.calculate_quadrature_sample_points() {
+                painter.circle_filled(
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x, self.window_duration),
+                            y_offset(rect, y, y_range),
+                        ),
+                    4.0,
+                    Color32::ORANGE,
+                );
+            }
+        }
+
+        // Draw vertical lines at sample points
+        for (x, _) in sample_points {
+            let x_pos = rect.left_top().x + x_offset(rect, *x, self.window_duration);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(x_pos, rect.top()),
+                    egui::Pos2::new(x_pos, rect.bottom()),
+                ],
+                Stroke::new(1.0, Color32::from_rgba_premultiplied(255, 0, 0, 100)),
+            );
+        }
+
+        // Draw sample points
+        for (x, y) in sample_points {
+            painter.circle_filled(
+                rect.left_top()
+                    + vec2(
+                        x_offset(rect, *x, self.window_duration),
+                        y_offset(rect, *y, y_range),
+                    ),
+                4.0,
+                Color32::RED,
+            );
+        }
+
+        // draw_axis_labels(painter, rect, "Time", "Amplitude");
+
+        // Add legend
+        let legend_bottom = if self.signal.is_complex() { 90.0 } else { 50.0 };
+        painter.rect_filled(
+            egui::Rect::from_min_max(
+                egui::Pos2::new(rect.right() - 120.0, rect.top() + 10.0),
+                egui::Pos2::new(rect.right() - 10.0, rect.top() + legend_bottom),
+            ),
+            3.0,
+            Color32::from_rgba_premultiplied(40, 40, 40, 200),
+        );
+
+        painter.line_segment(
+            [
+                egui::Pos2::new(rect.right() - 110.0, rect.top() + 20.0),
+                egui::Pos2::new(rect.right() - 90.0, rect.top() + 20.0),
+            ],
+            Stroke::new(2.0, Color32::GREEN),
+        );
+
+        painter.circle_filled(
+            egui::Pos2::new(rect.right() - 100.0, rect.top() + 40.0),
+            4.0,
+            Color32::RED,
+        );
+
+        painter.text(
+            egui::Pos2::new(rect.right() - 80.0, rect.top() + 20.0),
+            egui::Align2::LEFT_CENTER,
+            "Signal",
+            egui::FontId::proportional(12.0),
+            Color32::YELLOW,
+        );
+
+        painter.text(
+            egui::Pos2::new(rect.right() - 80.0, rect.top() + 40.0),
+            egui::Align2::LEFT_CENTER,
+            "Samples",
+            egui::FontId::proportional(12.0),
+            Color32::YELLOW,
+        );
+
+        if self.signal.is_complex() {
+            painter.line_segment(
+                [
+                    egui::Pos2::new(rect.right() - 110.0, rect.top() + 60.0),
+                    egui::Pos2::new(rect.right() - 90.0, rect.top() + 60.0),
+                ],
+                Stroke::new(2.0, Color32::from_rgb(0, 170, 255)),
+            );
+
+            painter.circle_filled(
+                egui::Pos2::new(rect.right() - 100.0, rect.top() + 80.0),
+                4.0,
+                Color32::ORANGE,
+            );
+
+            painter.text(
+                egui::Pos2::new(rect.right() - 80.0, rect.top() + 60.0),
+                egui::Align2::LEFT_CENTER,
+                "Quadrature",
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+
+            painter.text(
+                egui::Pos2::new(rect.right() - 80.0, rect.top() + 80.0),
+                egui::Align2::LEFT_CENTER,
+                "Q samples",
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+        }
+    }
+}
+
+impl AliasApp {
+    fn render_sample_points_graph(
+        &mut self,
+        ui: &mut egui::Ui,
+        sample_points: Vec<(f32, f32)>,
+        plot_height: f32,
+        plot_width: f32,
+        y_range: (f32, f32),
+        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
+    ) {
+        ui.colored_label(Color32::YELLOW, format!("Sample points"));
+        let user_placed = self.sampling.mode == SamplingMode::UserPlaced;
+        let response2 = ui.allocate_rect(
+            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
+            if user_placed {
+                egui::Sense::click_and_drag()
+            } else {
+                egui::Sense::hover()
+            },
+        );
+
+        let rect = response2.rect.intersect(ui.clip_rect());
+        if user_placed {
+            self.edit_user_samples(&response2, rect);
+            // the plots below have to see the moved samples in this frame already
+            self.memo.key = self.memo_key();
+        }
+        let painter = ui.painter();
+
+        // Draw horizontal zero line
+        painter.line_segment(
+            [
+                egui::Pos2::new(rect.left(), rect.top() + y_offset(rect, 0.0, y_range)),
+                egui::Pos2::new(rect.right(), rect.top() + y_offset(rect, 0.0, y_range)),
+            ],
+            Stroke::new(1.0, Color32::YELLOW),
+        );
+
+        // Draw the quantization levels, and hold each sample until the next one like the ADC output
+        if self.adc.enabled {
+            let level_to_y = |level: f32| rect.top() + y_offset(rect, level, y_range);
+            let level_spacing = self.adc.lsb() / (y_range.1 - y_range.0) * rect.height();
+            // finer levels would just fill the plot
+            if level_spacing >= 3.0 {
+                for level in self.adc.levels_between(y_range.0, y_range.1) {
+                    painter.line_segment(
+                        [
+                            egui::Pos2::new(rect.left(), level_to_y(level)),
+                            egui::Pos2::new(rect.right(), level_to_y(level)),
+                        ],
+                        Stroke::new(1.0, Color32::from_rgba_premultiplied(60, 60, 60, 60)),
+                    );
+                }
+            }
+            for level in [-self.adc.full_scale, self.adc.full_scale] {
+                if (y_range.0..=y_range.1).contains(&level) {
+                    painter.line_segment(
+                        [
+                            egui::Pos2::new(rect.left(), level_to_y(level)),
+                            egui::Pos2::new(rect.right(), level_to_y(level)),
+                        ],
+                        Stroke::new(1.0, Color32::from_rgba_premultiplied(150, 0, 0, 150)),
+                    );
+                }
+            }
+
+            let staircase: Vec<egui::Pos2> = sample_points
+                .windows(2)
+                .flat_map(|pair| {
+                    let (x1, y1) = pair[0];
+                    let (x2, _) = pair[1];
+                    [
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x1, self.window_duration),
+                                y_offset(rect, y1, y_range),
+                            ),
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x2, self.window_duration),
+                                y_offset(rect, y1, y_range),
+                            ),
+                    ]
+                })
+                .collect();
+            painter.add(egui::Shape::line(
+                staircase,
+                Stroke::new(1.0, Color32::from_rgb(255, 120, 120)),
+            ));
+        }
+
+        // Overlay the FFT window on the samples it weighs, from 0 at the bottom to 1 at the top
+        if self.fft_window.kind != WindowKind::Rectangular {
+            let n_samples = self.fft_signal_size();
+            let sampling_offset = self.sampling.offset(self.sampling_frequency);
+            let points = self
+                .fft_window
+                .coefficients(n_samples)
+                .into_iter()
+                .enumerate()
+                .map(|(i, w)| (sampling_offset + i as f32 / self.sampling_frequency, w))
+                .take_while(|(t, _)| *t <= self.window_duration)
+                .map(|(t, w)| {
+                    egui::Pos2::new(
+                        rect.left() + x_offset(rect, t, self.window_duration),
+                        rect.bottom() - w * rect.height(),
+                    )
+                })
+                .collect();
+            painter.add(egui::Shape::line(
+                points,
+                Stroke::new(1.5, Color32::from_rgba_premultiplied(200, 200, 0, 120)),
+            ));
+        }
+
+        // Draw sample points
+        for (x, y) in &sample_points {
+            painter.circle_filled(
+                rect.left_top()
+                    + vec2(
+                        x_offset(rect, *x, self.window_duration),
+                        y_offset(rect, *y, y_range),
+                    ),
+                4.0,
+                Color32::RED,
+            );
+        }
+
+        // Preview where the dragged sample goes, it's only taken there once released
+        if let Some((_, t)) = self.dragged_sample {
+            painter.circle_stroke(
+                rect.left_top()
+                    + vec2(
+                        x_offset(rect, t, self.window_duration),
+                        y_offset(rect, self.signal.value(t), y_range),
+                    ),
+                6.0,
+                Stroke::new(1.5, Color32::RED),
+            );
+        }
+
+        if self.signal.is_complex() {
+            for (x, y) in self.calculate_quadrature_sample_points() {
+                painter.circle_filled(
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x, self.window_duration),
+                            y_offset(rect, y, y_range),
+                        ),
+                    4.0,
+                    Color32::ORANGE,
+                );
+            }
+        }
+
+        // draw_axis_labels(painter, rect, "Time", "Amplitude");
+    }
+
+    /// Moves, adds and removes user placed samples with the pointer.
+    fn edit_user_samples(&mut self, response: &egui::Response, rect: egui::Rect) {
+        let user_times = &mut self.sampling.user_times;
+        let pointer_t = response.interact_pointer_pos().map(|pos| {
+            // keep the samples inside of one repetition of the pattern
+            ((pos.x - rect.left()) / rect.width() * self.window_duration)
+                .clamp(0.0, self.window_duration * 0.999)
+        });
+        let nearest = |user_times: &[f32], t: f32| {
+            user_times
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| (*a - t).abs().total_cmp(&(*b - t).abs()))
+                .map(|(i, _)| i)
+        };
+
+        if response.drag_started() {
+            self.dragged_sample = pointer_t
+                .and_then(|t| nearest(user_times, t))
+                .map(|i| (i, user_times[i]));
+        }
+        if let (true, Some((_, dragged_t)), Some(t)) =
+            (response.dragged(), self.dragged_sample.as_mut(), pointer_t)
+        {
+            *dragged_t = t;
+        }
+        if response.drag_stopped() {
+            if let Some((i, t)) = self.dragged_sample.take()
+                && let Some(sample_t) = user_times.get_mut(i)
+            {
+                *sample_t = t;
+            }
+            user_times.sort_by(f32::total_cmp);
+        }
+
+        match pointer_t {
+            Some(t) if response.clicked() => {
+                user_times.push(t);
+                user_times.sort_by(f32::total_cmp);
+            }
+            Some(t) if response.secondary_clicked() && user_times.len() > 1 => {
+                if let Some(i) = nearest(user_times, t) {
+                    user_times.remove(i);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl AliasApp {
+    fn render_fft(
+        &mut self,
+        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
+        rect: egui::Rect,
+        painter: &egui::Painter,
+        fft_size: usize,
+        fft_output: &Vec<Complex<f32>>,
+        padded_fft_output: &[Complex<f32>],
+    ) {
+        // Show at least 0-20Hz, and the whole sampling frequency for the higher rates of WAV files.
+        // A complex signal has no mirrored half, its spectrum is shown over -fs/2..fs/2 instead.
+        let complex = self.signal.is_complex();
+        let (min_display_freq, max_display_freq) = if complex {
+            (
+                -self.sampling_frequency / 2.0,
+                self.sampling_frequency / 2.0,
+            )
+        } else {
+            (0.0, self.sampling_frequency.max(20.0))
+        };
+        let freq_to_x = |freq: f32| {
+            rect.left()
+                + (freq - min_display_freq) / (max_display_freq - min_display_freq) * rect.width()
+        };
+        let is_displayed = |freq: f32| (min_display_freq..=max_display_freq).contains(&freq);
+
+        // Calculate how many points to display
+        let freq_resolution = self.sampling_frequency / fft_size as f32;
+
+        // Calculate magnitudes
+        let magnitudes: Vec<f32> = fft_output //[..display_points]
+            .iter()
+            .map(|c| c.norm() / fft_size as f32)
+            .collect::<Vec<f32>>();
+
+        // Bin 0 (DC) has no mirrored partner, so unlike the other bins it isn't doubled.
+        // The bins of a complex signal aren't mirrored at all.
+        let bar_scale = |i_bucket: usize| if complex || i_bucket == 0 { 1.0 } else { 2.0 };
+
+        // Autoscale once a single bar would exceed an amplitude of 1
+        let full_scale = magnitudes
+            .iter()
+            .enumerate()
+            .map(|(i, m)| m * bar_scale(i))
+            .fold(1.0, f32::max);
+
+        // Convert a peak amplitude to the selected scale, a real tone's RMS is amplitude / √2.
+        // The PSD spreads the power of each bin over the noise bandwidth of the window.
+        let magnitude_scale = self.magnitude_scale;
+        let reference_amplitude = if self.adc.enabled {
+            self.adc.full_scale
+        } else {
+            1.0
+        };
+        let enbw_hz = self.calculate_window_metrics().enbw * freq_resolution;
+        let to_display = |amplitude: f32, dc: bool| {
+            let rms = if complex || dc {
+                amplitude
+            } else {
+                amplitude / std::f32::consts::SQRT_2
+            };
+            match magnitude_scale {
+                MagnitudeScale::Linear => amplitude,
+                MagnitudeScale::Dbfs => 20.0 * (amplitude / reference_amplitude).max(1e-20).log10(),
+                MagnitudeScale::Dbv => 20.0 * rms.max(1e-20).log10(),
+                MagnitudeScale::Psd => 10.0 * (rms * rms / enbw_hz).max(1e-30).log10(),
+            }
+        };
+
+        // The dB scales reach from the floor up to the next multiple of 10 dB above the highest bar
+        let (min_value, max_value) = if magnitude_scale == MagnitudeScale::Linear {
+            (0.0, full_scale)
+        } else {
+            let top = magnitudes
+                .iter()
+                .enumerate()
+                .map(|(i, m)| to_display(m * bar_scale(i), i == 0))
+                .fold(f32::NEG_INFINITY, f32::max);
+            let top = (top / 10.0).ceil() * 10.0;
+            (self.db_floor, top.max(self.db_floor + 10.0))
+        };
+        let value_to_y = |value: f32| {
+            rect.bottom()
+                - ((value - min_value) / (max_value - min_value)).clamp(0.0, 1.0) * rect.height()
+        };
+        let amplitude_to_y = |amplitude: f32, dc: bool| value_to_y(to_display(amplitude, dc));
+
+        // Draw the ticks of the vertical axis
+        let value_step = tick_step(max_value - min_value, 4);
+        for i_tick in (min_value / value_step).ceil() as i32..=(max_value / value_step) as i32 {
+            let value = i_tick as f32 * value_step;
+            let y = value_to_y(value);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(rect.left(), y),
+                    egui::Pos2::new(rect.left() + 5.0, y),
+                ],
+                Stroke::new(1.0, Color32::YELLOW),
+            );
+            painter.text(
+                egui::Pos2::new(rect.left() + 8.0, y),
+                egui::Align2::LEFT_CENTER,
+                format!(
+                    "{} {}",
+                    (value * 1000.0).round() / 1000.0,
+                    magnitude_scale.unit()
+                ),
+                egui::FontId::proportional(11.0),
+                Color32::GRAY,
+            );
+        }
+
+        // Draw horizontal zero line
+        painter.line_segment(
+            [
+                egui::Pos2::new(rect.left(), rect.bottom()),
+                egui::Pos2::new(rect.right(), rect.bottom()),
+            ],
+            Stroke::new(1.0, Color32::YELLOW),
+        );
+
+        // Draw vertical axis line at 0 Hz
+        painter.line_segment(
+            [
+                egui::Pos2::new(freq_to_x(0.0), rect.top()),
+                egui::Pos2::new(freq_to_x(0.0), rect.bottom()),
+            ],
+            Stroke::new(1.0, Color32::YELLOW),
+        );
+
+        // Draw frequency ticks, every 5 Hz for the default range of 0-20Hz
+        let tick_step = tick_step(max_display_freq - min_display_freq, 4);
+        for i_tick in
+            (min_display_freq / tick_step).ceil() as i32..=(max_display_freq / tick_step) as i32
+        {
+            let freq = i_tick as f32 * tick_step;
+            let x_pos = freq_to_x(freq);
+
+            // Draw tick
+            painter.line_segment(
+                [
+                    egui::Pos2::new(x_pos, rect.bottom()),
+                    egui::Pos2::new(x_pos, rect.bottom() + 5.0),
+                ],
+                Stroke::new(1.0, Color32::YELLOW),
+            );
+
+            // Draw tick label
+            painter.text(
+                egui::Pos2::new(x_pos, rect.bottom() + 15.0),
+                egui::Align2::CENTER_CENTER,
+                format!("{freq} Hz"),
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+        }
+
+        for (i, line) in self.signal.spectral_lines().iter().enumerate() {
+            // stagger the labels, so that carrier and sidebands don't overlap
+            let label_offset = 30.0 * (i % 3) as f32;
+
+            // Mark signal frequency position
+            if is_displayed(line.frequency) {
+                let signal_freq_x = freq_to_x(line.frequency);
+                painter.line_segment(
+                    [
+                        egui::Pos2::new(signal_freq_x, rect.top()),
+                        egui::Pos2::new(signal_freq_x, rect.bottom()),
+                    ],
+                    Stroke::new(1.0, Color32::RED),
+                );
+
+                painter.text(
+                    egui::Pos2::new(signal_freq_x, rect.top() + 15.0 + label_offset),
+                    egui::Align2::CENTER_CENTER,
+                    match line.label {
+                        Some(label) => format!("{label}: {:.1} Hz", line.frequency),
+                        None => format!("{:.1} Hz", line.frequency),
+                    },
+                    egui::FontId::proportional(12.0),
+                    Color32::YELLOW,
+                );
+            }
+
+            // Add aliased frequency label if applicable
+            if self.is_aliased(line.frequency) {
+                let alias_freq = self.apparent_frequency(line.frequency);
+
+                if is_displayed(alias_freq) {
+                    let alias_x = freq_to_x(alias_freq);
+                    painter.line_segment(
+                        [
+                            egui::Pos2::new(alias_x, rect.top()),
+                            egui::Pos2::new(alias_x, rect.bottom()),
+                        ],
+                        Stroke::new(1.0, Color32::from_rgb(128, 0, 128)), // Purple
+                    );
+
+                    painter.text(
+                        egui::Pos2::new(alias_x + 50.0, rect.top() + 30.0 + label_offset),
+                        egui::Align2::CENTER_CENTER,
+                        match line.label {
+                            Some(label) => format!("{label} alias: {alias_freq:.1} Hz"),
+                            None => format!("Alias: {alias_freq:.1} Hz"),
+                        },
+                        egui::FontId::proportional(12.0),
+                        Color32::RED,
+                    );
+                }
+            }
+        }
+
+        for component in self.signal.active_components() {
+            // Mark where the harmonics above Nyquist fold back to
+            if component.waveform != Waveform::Sine && !component.band_limited {
+                for k in 2..=MARKED_HARMONICS {
+                    let (amplitude, _) = component.waveform.harmonic(k, component.duty_cycle);
+                    let harmonic_freq = k as f32 * component.frequency;
+                    if amplitude.abs() < 1e-6 || harmonic_freq <= self.sampling_frequency / 2.0 {
+                        continue;
+                    }
+
+                    let alias_x =
+                        freq_to_x(alias_frequency(harmonic_freq, self.sampling_frequency));
+                    painter.line_segment(
+                        [
+                            egui::Pos2::new(alias_x, rect.top() + 40.0),
+                            egui::Pos2::new(alias_x, rect.bottom()),
+                        ],
+                        Stroke::new(1.0, Color32::from_rgba_premultiplied(128, 0, 128, 100)),
+                    );
+                }
+            }
+        }
+
+        // Overlay the magnitude response of the anti-aliasing filter, 1 at the top of the plot
+        if self.filter.enabled {
+            let n_points = rect.width().max(2.0) as usize;
+            let poles = self.calculate_filter_poles();
+            let response: Vec<egui::Pos2> = (0..=n_points)
+                .map(|i| {
+                    let freq = min_display_freq
+                        + (max_display_freq - min_display_freq) * i as f32 / n_points as f32;
+                    let gain = self.filter.response_with(&poles, freq.abs()).min(1.0);
+                    egui::Pos2::new(freq_to_x(freq), rect.bottom() - gain * rect.height())
+                })
+                .collect();
+            painter.add(egui::Shape::line(
+                response,
+                Stroke::new(1.5, Color32::from_rgb(0, 200, 200)),
+            ));
+            painter.text(
+                egui::Pos2::new(freq_to_x(self.filter.cutoff), rect.top() + 5.0),
+                egui::Align2::LEFT_TOP,
+                format!("{} |H(f)|", self.filter.kind.name()),
+                egui::FontId::proportional(12.0),
+                Color32::from_rgb(0, 200, 200),
+            );
+        }
+
+        // Shade the sweep range of a chirp or the band of a bandpass signal,
+        // and the bands its part above Nyquist folds back to
+        let shaded_band = match self.signal.kind {
+            SignalKind::Chirp => Some(("Sweep", self.signal.chirp.frequency_range())),
+            SignalKind::Bandpass => Some(("Band", self.signal.bandpass.band())),
+            _ => None,
+        };
+        if let Some((name, (f_min, f_max))) = shaded_band {
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::Pos2::new(freq_to_x(f_min), rect.top()),
+                    egui::Pos2::new(freq_to_x(f_max), rect.bottom()),
+                ),
+                0.0,
+                Color32::from_rgba_premultiplied(60, 0, 0, 60),
+            );
+            painter.text(
+                egui::Pos2::new(freq_to_x(f_min) + 5.0, rect.top() + 15.0),
+                egui::Align2::LEFT_CENTER,
+                format!("{name}: {f_min:.1} .. {f_max:.1} Hz"),
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+
+            let nyquist_freq = self.sampling_frequency / 2.0;
+            if f_max > nyquist_freq {
+                for (low, high) in
+                    alias_range(nyquist_freq.max(f_min), f_max, self.sampling_frequency)
+                {
+                    painter.rect_filled(
+                        egui::Rect::from_min_max(
+                            egui::Pos2::new(freq_to_x(low), rect.center().y),
+                            egui::Pos2::new(
+                                freq_to_x(high).max(freq_to_x(low) + 1.0),
+                                rect.bottom(),
+                            ),
+                        ),
+                        0.0,
+                        Color32::from_rgba_premultiplied(64, 0, 64, 60), // Purple
+                    );
+                }
+                let inverted = self.signal.kind == SignalKind::Bandpass
+                    && self.signal.bandpass.is_inverted(self.sampling_frequency);
+                painter.text(
+                    egui::Pos2::new(rect.left() + 5.0, rect.center().y + 15.0),
+                    egui::Align2::LEFT_CENTER,
+                    format!(
+                        "Aliased part of the {}{}",
+                        name.to_lowercase(),
+                        if inverted { " (inverted)" } else { "" }
+                    ),
+                    egui::FontId::proportional(12.0),
+                    Color32::RED,
+                );
+            }
+        }
+
+        // Mark Nyquist frequency if it's in our display range, for complex signals on both sides
+        let nyquist_freq = self.sampling_frequency / 2.0;
+        let nyquist_markers = if complex {
+            vec![
+                (-nyquist_freq, egui::Align2::LEFT_BOTTOM),
+                (nyquist_freq, egui::Align2::RIGHT_BOTTOM),
+            ]
+        } else {
+            vec![(nyquist_freq, egui::Align2::CENTER_BOTTOM)]
+        };
+        for (freq, align) in nyquist_markers {
+            if !is_displayed(freq) {
+                continue;
+            }
+            let nyquist_x = freq_to_x(freq);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(nyquist_x, rect.top()),
+                    egui::Pos2::new(nyquist_x, rect.bottom()),
+                ],
+                Stroke::new(1.0, Color32::from_rgba_premultiplied(255, 255, 0, 100)),
+            );
+
+            painter.text(
+                egui::Pos2::new(nyquist_x, rect.bottom() - 5.0),
+                align,
+                format!("Nyquist: {freq:.1} Hz"),
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+        }
+
+        // Draw FFT bars
+        if !magnitudes.is_empty() {
+            // For each display bucket, position it according to its frequency
+            for i_bucket in 0..magnitudes.len() {
+                // Calculate the frequency this bucket represents,
+                // for complex signals the upper half of the bins are the negative frequencies
+                let bucket_freq = if complex && i_bucket >= magnitudes.len() / 2 {
+                    (i_bucket as f32 - magnitudes.len() as f32) * freq_resolution
+                } else {
+                    i_bucket as f32 * freq_resolution
+                };
+
+                // Position the bucket according to its frequency (scaled to display width)
+                let x = freq_to_x(bucket_freq);
+
+                // Calculate width based on frequency resolution
+                let next_x = freq_to_x(bucket_freq + freq_resolution);
+                let bucket_width = f32::max(next_x - x, 1.0);
+
+                let y = amplitude_to_y(magnitudes[i_bucket] * bar_scale(i_bucket), i_bucket == 0);
+
+                painter.rect_filled(
+                    egui::Rect::from_min_max(
+                        egui::Pos2::new(x, y),
+                        egui::Pos2::new(x + bucket_width * 0.9, rect.bottom()),
+                    ),
+                    0.0,
+                    Color32::LIGHT_BLUE,
+                );
+            }
+        }
+
+        // Draw the zero padded spectrum as a curve through the bars, normalized like them
+        // to the number of samples rather than the padded length
+        if !padded_fft_output.is_empty() {
+            let padded_size = padded_fft_output.len();
+            let padded_resolution = self.sampling_frequency / padded_size as f32;
+            let mut points: Vec<(f32, egui::Pos2)> = padded_fft_output
+                .iter()
+                .enumerate()
+                .map(|(k, c)| {
+                    let freq = if complex && k >= padded_size / 2 {
+                        (k as f32 - padded_size as f32) * padded_resolution
+                    } else {
+                        k as f32 * padded_resolution
+                    };
+                    let y = amplitude_to_y(c.norm() / fft_size as f32 * bar_scale(k), k == 0);
+                    // the bars are drawn to the right of their frequency
+                    let x = freq_to_x(freq) + 0.45 * (freq_to_x(freq_resolution) - freq_to_x(0.0));
+                    (freq, egui::Pos2::new(x, y))
+                })
+                .filter(|(freq, _)| is_displayed(*freq))
+                .collect();
+            points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            painter.add(egui::Shape::line(
+                points.into_iter().map(|(_, point)| point).collect(),
+                Stroke::new(1.5, Color32::from_rgb(255, 220, 100)),
+            ));
+        }
+
+        // Overlay the spectrum of the samples at the instants they were actually taken at
+        if self.sampling.mode != SamplingMode::Uniform {
+            let nudft = self.calculate_nudft(fft_size, (min_display_freq, max_display_freq));
+            let points = nudft
+                .iter()
+                .map(|(freq, magnitude)| {
+                    // like the bars, the magnitude of a real signal is split between f and -f
+                    let scale = if complex || *freq == 0.0 { 1.0 } else { 2.0 };
+                    egui::Pos2::new(
+                        freq_to_x(*freq),
+                        amplitude_to_y(magnitude * scale, *freq == 0.0),
+                    )
+                })
+                .collect();
+            painter.add(egui::Shape::line(points, Stroke::new(1.5, Color32::ORANGE)));
+            painter.text(
+                egui::Pos2::new(rect.right() - 5.0, rect.top() + 15.0),
+                egui::Align2::RIGHT_CENTER,
+                "Non-uniform DFT (actual sample instants)",
+                egui::FontId::proportional(12.0),
+                Color32::ORANGE,
+            );
+        }
+
+        // Draw the noise floor, estimated as the median bar height (tones only occupy a few bins)
+        if self.noise.enabled && magnitudes.len() > 2 {
+            // the upper half of the bins of a real signal is just the mirror image
+            let mut distinct = if complex {
+                magnitudes[1..].to_vec()
+            } else {
+                magnitudes[1..=magnitudes.len() / 2].to_vec()
+            };
+            distinct.sort_by(f32::total_cmp);
+            let noise_floor = distinct[distinct.len() / 2] * bar_scale(1);
+
+            let y = amplitude_to_y(noise_floor, false);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(rect.left(), y),
+                    egui::Pos2::new(rect.right(), y),
+                ],
+                Stroke::new(1.0, Color32::GRAY),
+            );
+            painter.text(
+                egui::Pos2::new(rect.right() - 5.0, y - 8.0),
+                egui::Align2::RIGHT_CENTER,
+                if magnitude_scale == MagnitudeScale::Linear {
+                    format!("Noise floor: {noise_floor:.4}")
+                } else {
+                    format!(
+                        "Noise floor: {:.1} {}",
+                        to_display(noise_floor, false),
+                        magnitude_scale.unit()
+                    )
+                },
+                egui::FontId::proportional(12.0),
+                Color32::GRAY,
+            );
+        }
+
+        // Label the DC component in bin 0
+        if self.signal.dc_offset != 0.0 {
+            painter.text(
+                egui::Pos2::new(rect.left() + 5.0, rect.top() + 45.0),
+                egui::Align2::LEFT_CENTER,
+                format!("DC: {:.2}", self.signal.dc_offset),
+                egui::FontId::proportional(12.0),
+                Color32::LIGHT_BLUE,
+            );
+        }
+
+        // draw_axis_labels(painter, rect, "Frequency (Hz)", "Magnitude");
+    }
+}
+
+impl AliasApp {
+    fn render_fft_raw_values(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        fft_output: &Vec<Complex<f32>>,
+    ) {
+        let fft_size = fft_output.len();
+        let freq_resolution = self.sampling_frequency / fft_size as f32;
+        let complex = self.signal.is_complex();
+
+        egui::Window::new("Raw FFT Values")
+            .open(&mut self.show_raw_fft_values)
+            .resizable(true)
+            .default_width(400.0)
+            .max_height(600.0)
+            .show(ctx, |ui| {
+                ui.label(format!("FFT Size: {}", fft_size));
+                ui.label(format!("Frequency Resolution: {:.4} Hz", freq_resolution));
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(500.0)
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Bin");
+                            ui.separator();
+                            ui.label("Frequency (Hz)");
+                            ui.separator();
+                            ui.label("Real");
+                            ui.separator();
+                            ui.label("Imaginary");
+                            ui.separator();
+                            ui.label("Magnitude / N");
+                            ui.separator();
+                            ui.label("Phase (rad) / π");
+                        });
+                        ui.separator();
+
+                        for (i, complex_val) in fft_output.iter().enumerate() {
+                            let freq = if complex && i >= fft_size / 2 {
+                                (i as f32 - fft_size as f32) * freq_resolution
+                            } else {
+                                i as f32 * freq_resolution
+                            };
+                            let magnitude = complex_val.norm() / fft_size as f32;
+                            let phase = complex_val.arg() / PI;
+
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{:4}", i));
+                                ui.separator();
+                                ui.label(format!("{:8.3}", freq));
+                                ui.separator();
+                                ui.label(format!("{:10.6}", complex_val.re));
+                                ui.separator();
+                                ui.label(format!("{:10.6}", complex_val.im));
+                                ui.separator();
+                                ui.label(format!("{:10.6}", magnitude));
+                                ui.separator();
+                                ui.label(format!("{:8.3}", phase));
+                            });
+                        }
+                    });
+            });
+    }
+}
+
+impl AliasApp {
+    /// Draws the phase of each bin of `fft_output` that is strong enough to carry a tone.
+    fn render_phase(
+        &mut self,
+        ui: &mut egui::Ui,
+        fft_output: &[Complex<f32>],
+        plot_height: f32,
+        plot_width: f32,
+    ) {
+        ui.colored_label(
+            Color32::YELLOW,
+            format!(
+                "Phase (bins within {:.0} dB of the strongest{})",
+                -self.phase_plot.threshold_db,
+                if self.phase_plot.unwrap {
+                    ", unwrapped"
+                } else {
+                    ""
+                }
+            ),
+        );
+        let response = ui.allocate_rect(
+            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
+            egui::Sense::hover(),
+        );
+        let rect = response.rect.intersect(ui.clip_rect());
+        let painter = ui.painter().with_clip_rect(rect);
+
+        let fft_size = fft_output.len();
+        if fft_size == 0 {
+            return;
+        }
+
+        // the same frequency axis as the magnitude plot above
+        let complex = self.signal.is_complex();
+        let (min_display_freq, max_display_freq) = if complex {
+            (
+                -self.sampling_frequency / 2.0,
+                self.sampling_frequency / 2.0,
+            )
+        } else {
+            (0.0, self.sampling_frequency.max(20.0))
+        };
+        let freq_to_x = |freq: f32| {
+            rect.left()
+                + (freq - min_display_freq) / (max_display_freq - min_display_freq) * rect.width()
+        };
+        let freq_resolution = self.sampling_frequency / fft_size as f32;
+
+        // the bins above the threshold, in the order of their frequency
+        let max_magnitude = fft_output.iter().map(|c| c.norm()).fold(0.0, f32::max);
+        let threshold = max_magnitude * 10f32.powf(self.phase_plot.threshold_db / 20.0);
+        let mut phases: Vec<(f32, f32)> = fft_output
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| max_magnitude > 0.0 && c.norm() >= threshold)
+            .map(|(i_bucket, c)| {
+                let freq = if complex && i_bucket >= fft_size / 2 {
+                    (i_bucket as f32 - fft_size as f32) * freq_resolution
+                } else {
+                    i_bucket as f32 * freq_resolution
+                };
+                (freq, c.arg())
+            })
+            .collect();
+        phases.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        if self.phase_plot.unwrap {
+            for i in 1..phases.len() {
+                let jump = phases[i].1 - phases[i - 1].1;
+                phases[i].1 -= 2.0 * PI * (jump / (2.0 * PI)).round();
+            }
+        }
+
+        // -π..π, or as far as the unwrapped phase reaches
+        let (min_phase, max_phase) = phases.iter().fold((-PI, PI), |(low, high), (_, phase)| {
+            (low.min(*phase), high.max(*phase))
+        });
+        let phase_to_y = |phase: f32| {
+            rect.bottom() - (phase - min_phase) / (max_phase - min_phase) * rect.height()
+        };
+
+        // Draw the zero line and the ticks in multiples of π
+        painter.line_segment(
+            [
+                egui::Pos2::new(rect.left(), phase_to_y(0.0)),
+                egui::Pos2::new(rect.right(), phase_to_y(0.0)),
+            ],
+            Stroke::new(1.0, Color32::YELLOW),
+        );
+        let step = tick_step((max_phase - min_phase) / PI, 4);
+        for i_tick in (min_phase / PI / step).ceil() as i32..=(max_phase / PI / step).floor() as i32
+        {
+            let value = i_tick as f32 * step;
+            let y = phase_to_y(value * PI);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(rect.left(), y),
+                    egui::Pos2::new(rect.left() + 5.0, y),
+                ],
+                Stroke::new(1.0, Color32::YELLOW),
+            );
+            painter.text(
+                egui::Pos2::new(rect.left() + 8.0, y),
+                egui::Align2::LEFT_CENTER,
+                format!("{}π", (value * 1000.0).round() / 1000.0),
+                egui::FontId::proportional(11.0),
+                Color32::GRAY,
+            );
+        }
+
+        // Draw a stem for each bin, in the middle of where its bar is drawn above
+        let bar_offset = 0.45 * (freq_to_x(freq_resolution) - freq_to_x(0.0));
+        for (freq, phase) in &phases {
+            if !(min_display_freq..=max_display_freq).contains(freq) {
+                continue;
+            }
+            let x = freq_to_x(*freq) + bar_offset;
+            let y = phase_to_y(*phase);
+            painter.line_segment(
+                [egui::Pos2::new(x, phase_to_y(0.0)), egui::Pos2::new(x, y)],
+                Stroke::new(1.0, Color32::LIGHT_BLUE),
+            );
+            painter.circle_filled(egui::Pos2::new(x, y), 3.0, Color32::LIGHT_BLUE);
+        }
+    }
+}
+
+impl AliasApp {
+    fn render_reconstructed(
+        &mut self,
+        ui: &mut egui::Ui,
+        signal: Vec<(f32, f32)>,
+        plot_height: f32,
+        plot_width: f32,
+        y_range: (f32, f32),
+        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
+        recon_signal: Vec<(f32, Complex<f32>)>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                Color32::YELLOW,
+                format!(
+                    "Reconstructed signal ({})",
+                    self.reconstruction.method.name()
+                ),
+            );
+            if self.reconstruction.method == ReconstructionMethod::Fft
+                && self.fft_signal_size() > MAX_FFT_RECONSTRUCTION_SIZE
+            {
+                ui.colored_label(
+                    Color32::ORANGE,
+                    format!(
+                        "more than {MAX_FFT_RECONSTRUCTION_SIZE} bins, reconstructed with the truncated sinc instead"
+                    ),
+                );
+            }
+        });
+        let response4 = ui.allocate_rect(
+            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
+            egui::Sense::hover(),
+        );
+
+        let rect = response4.rect.intersect(ui.clip_rect());
+        let painter = ui.painter();
+
+        // Draw horizontal zero line
+        painter.line_segment(
+            [
+                egui::Pos2::new(rect.left(), rect.top() + y_offset(rect, 0.0, y_range)),
+                egui::Pos2::new(rect.right(), rect.top() + y_offset(rect, 0.0, y_range)),
+            ],
+            Stroke::new(1.0, Color32::YELLOW),
+        );
+
+        // Draw reconstructed signal, and the quadrature part of a complex one
+        for i in 0..recon_signal.len() - 1 {
+            let (x1, y1) = recon_signal[i];
+            let (x2, y2) = recon_signal[i + 1];
+
+            painter.line_segment(
+                [
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x1, self.window_duration),
+                            y_offset(rect, y1.re, y_range),
+                        ),
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x2, self.window_duration),
+                            y_offset(rect, y2.re, y_range),
+                        ),
+                ],
+                Stroke::new(4.0, Color32::RED),
+            );
+
+            if self.signal.is_complex() {
+                painter.line_segment(
+                    [
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x1, self.window_duration),
+                                y_offset(rect, y1.im, y_range),
+                            ),
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x2, self.window_duration),
+                                y_offset(rect, y2.im, y_range),
+                            ),
+                    ],
+                    Stroke::new(4.0, Color32::from_rgb(200, 100, 0)),
+                );
+            }
+        }
+
+        if self.signal.is_complex() {
+            let quadrature_signal = self.calculate_quadrature_signal(signal.len() as u32);
+            for i in 0..quadrature_signal.len() - 1 {
+                let (x1, y1) = quadrature_signal[i];
+                let (x2, y2) = quadrature_signal[i + 1];
+                painter.line_segment(
+                    [
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x1, self.window_duration),
+                                y_offset(rect, y1, y_range),
+                            ),
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x2, self.window_duration),
+                                y_offset(rect, y2, y_range),
+                            ),
+                    ],
+                    Stroke::new(1.0, Color32::from_rgb(0, 170, 255)),
+                );
+            }
+        }
+
+        // Draw original signal for comparison (thinner line)
+        for i in 0..signal.len() - 1 {
+            let (x1, y1) = signal[i];
+            let (x2, y2) = signal[i + 1];
+            painter.line_segment(
+                [
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x1, self.window_duration),
+                            y_offset(rect, y1, y_range),
+                        ),
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x2, self.window_duration),
+                            y_offset(rect, y2, y_range),
+                        ),
+                ],
+                Stroke::new(1.0, Color32::GREEN),
+            );
+        }
+
+        // Draw sample points
+        let sample_points = self.calculate_sample_points();
+        for (x, y) in &sample_points {
+            painter.circle_filled(
+                rect.left_top()
+                    + vec2(
+                        x_offset(rect, *x, self.window_duration),
+                        y_offset(rect, *y, y_range),
+                    ),
+                4.0,
+                Color32::GREEN,
+            );
+        }
+
+        // draw_axis_labels(painter, rect, "Time", "Amplitude");
+
+        // Add legend
+        let legend_bottom = if self.signal.is_complex() { 90.0 } else { 70.0 };
+        painter.rect_filled(
+            egui::Rect::from_min_max(
+                egui::Pos2::new(rect.right() - 160.0, rect.top() + 10.0),
+                egui::Pos2::new(rect.right() - 10.0, rect.top() + legend_bottom),
+            ),
+            3.0,
+            Color32::from_rgba_premultiplied(40, 40, 40, 200),
+        );
+
+        painter.line_segment(
+            [
+                egui::Pos2::new(rect.right() - 150.0, rect.top() + 20.0),
+                egui::Pos2::new(rect.right() - 130.0, rect.top() + 20.0),
+            ],
+            Stroke::new(1.0, Color32::GREEN),
+        );
+
+        painter.line_segment(
+            [
+                egui::Pos2::new(rect.right() - 150.0, rect.top() + 40.0),
+                egui::Pos2::new(rect.right() - 130.0, rect.top() + 40.0),
+            ],
+            Stroke::new(4.0, Color32::RED),
+        );
 
         painter.circle_filled(
-            egui::Pos2::new(rect.right() - 100.0, rect.top() + 40.0),
+            egui::Pos2::new(rect.right() - 140.0, rect.top() + 60.0),
             4.0,
-            Color32::RED,
+            Color32::GREEN,
         );
 
         painter.text(
-            egui::Pos2::new(rect.right() - 80.0, rect.top() + 20.0),
+            egui::Pos2::new(rect.right() - 120.0, rect.top() + 20.0),
             egui::Align2::LEFT_CENTER,
-            "Signal",
+            "Original Signal",
             egui::FontId::proportional(12.0),
             Color32::YELLOW,
         );
 
         painter.text(
-            egui::Pos2::new(rect.right() - 80.0, rect.top() + 40.0),
+            egui::Pos2::new(rect.right() - 120.0, rect.top() + 40.0),
             egui::Align2::LEFT_CENTER,
-            "Samples",
+            "Reconstructed",
+            egui::FontId::proportional(12.0),
+            Color32::YELLOW,
+        );
+
+        painter.text(
+            egui::Pos2::new(rect.right() - 120.0, rect.top() + 60.0),
+            egui::Align2::LEFT_CENTER,
+            "Sample Points",
             egui::FontId::proportional(12.0),
             Color32::YELLOW,
         );
+
+        if self.signal.is_complex() {
+            painter.line_segment(
+                [
+                    egui::Pos2::new(rect.right() - 150.0, rect.top() + 80.0),
+                    egui::Pos2::new(rect.right() - 130.0, rect.top() + 80.0),
+                ],
+                Stroke::new(4.0, Color32::from_rgb(200, 100, 0)),
+            );
+
+            painter.text(
+                egui::Pos2::new(rect.right() - 120.0, rect.top() + 80.0),
+                egui::Align2::LEFT_CENTER,
+                "Reconstructed Q",
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+        }
+        self.render_reconstruction_error(ui, &signal, &recon_signal, plot_width, plot_height / 2.0);
     }
-}
 
-impl AliasApp {
-    fn render_sample_points_graph(
+    /// Plots the original minus the reconstructed signal, with its RMSE, maximum and the SNR
+    /// of the reconstruction.
+    fn render_reconstruction_error(
         &mut self,
         ui: &mut egui::Ui,
-        sample_points: Vec<(f32, f32)>,
-        plot_height: f32,
+        signal: &[(f32, f32)],
+        recon_signal: &[(f32, Complex<f32>)],
         plot_width: f32,
-        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
+        plot_height: f32,
     ) {
-        ui.colored_label(Color32::YELLOW, format!("Sample points"));
-        let response2 = ui.allocate_rect(
+        // the original signal as complex numbers, to compare both parts of complex signals
+        let original: Vec<Complex<f32>> = if self.signal.is_complex() {
+            let quadrature_signal = self.calculate_quadrature_signal(signal.len() as u32);
+            signal
+                .iter()
+                .zip(&quadrature_signal)
+                .map(|((_, i), (_, q))| Complex::new(*i, *q))
+                .collect()
+        } else {
+            signal.iter().map(|(_, y)| Complex::new(*y, 0.0)).collect()
+        };
+        let error: Vec<(f32, Complex<f32>)> = original
+            .iter()
+            .zip(recon_signal)
+            .map(|(x, (t, y))| (*t, x - y))
+            .collect();
+        if error.len() < 2 {
+            return;
+        }
+
+        let n = error.len() as f32;
+        let error_power = error.iter().map(|(_, e)| e.norm_sqr()).sum::<f32>() / n;
+        let signal_power = original.iter().map(|x| x.norm_sqr()).sum::<f32>() / n;
+        let max_error = error.iter().map(|(_, e)| e.norm()).fold(0.0, f32::max);
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                Color32::YELLOW,
+                "Reconstruction error (original - reconstructed)",
+            );
+            ui.colored_label(
+                Color32::LIGHT_BLUE,
+                format!(
+                    "RMSE: {:.4}, max: {max_error:.4}, SNR: {:.1} dB",
+                    error_power.sqrt(),
+                    10.0 * (signal_power / error_power).log10()
+                ),
+            );
+        });
+        let response = ui.allocate_rect(
             egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
             egui::Sense::hover(),
         );
 
-        let rect = response2.rect.intersect(ui.clip_rect());
+        let rect = response.rect.intersect(ui.clip_rect());
         let painter = ui.painter();
 
-        // Draw horizontal zero line
+        // scale to the largest error, so that small errors are still visible
+        let y_max = max_error.max(1e-6);
+        let y_range = (-y_max, y_max);
+
         painter.line_segment(
             [
-                egui::Pos2::new(rect.left(), rect.top() + rect.height() / 2.0),
-                egui::Pos2::new(rect.right(), rect.top() + rect.height() / 2.0),
+                egui::Pos2::new(rect.left(), rect.top() + y_offset(rect, 0.0, y_range)),
+                egui::Pos2::new(rect.right(), rect.top() + y_offset(rect, 0.0, y_range)),
             ],
             Stroke::new(1.0, Color32::YELLOW),
         );
 
-        // Draw sample points
-        for (x, y) in &sample_points {
-            painter.circle_filled(
-                rect.left_top()
-                    + vec2(
-                        *x / (2.0 * PI) * rect.width(),
-                        rect.height() / 2.0 - y * (rect.height() / 2.0),
-                    ),
-                4.0,
-                Color32::RED,
+        for i in 0..error.len() - 1 {
+            let (x1, y1) = error[i];
+            let (x2, y2) = error[i + 1];
+            painter.line_segment(
+                [
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x1, self.window_duration),
+                            y_offset(rect, y1.re, y_range),
+                        ),
+                    rect.left_top()
+                        + vec2(
+                            x_offset(rect, x2, self.window_duration),
+                            y_offset(rect, y2.re, y_range),
+                        ),
+                ],
+                Stroke::new(1.5, Color32::from_rgb(255, 150, 0)),
             );
+
+            if self.signal.is_complex() {
+                painter.line_segment(
+                    [
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x1, self.window_duration),
+                                y_offset(rect, y1.im, y_range),
+                            ),
+                        rect.left_top()
+                            + vec2(
+                                x_offset(rect, x2, self.window_duration),
+                                y_offset(rect, y2.im, y_range),
+                            ),
+                    ],
+                    Stroke::new(1.5, Color32::from_rgb(0, 170, 255)),
+                );
+            }
         }
 
-        // draw_axis_labels(painter, rect, "Time", "Amplitude");
+        painter.text(
+            rect.left_top() + vec2(5.0, 5.0),
+            egui::Align2::LEFT_TOP,
+            format!("±{y_max:.4}"),
+            egui::FontId::proportional(12.0),
+            Color32::GRAY,
+        );
     }
 }
 
 impl AliasApp {
-    fn render_fft(
-        &mut self,
-        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
-        rect: egui::Rect,
-        painter: &egui::Painter,
-        fft_size: usize,
-        fft_output: &Vec<Complex<f32>>,
-    ) {
-        let max_display_freq = 20.0;
+    /// The STFT of the samples the FFT analyzes, see [`Spectrogram::compute`].
+    fn calculate_spectrogram(&mut self, n_samples: usize) -> Vec<Vec<f32>> {
+        if let Some(ref memo) = self.memo.spectrogram {
+            if memo.key == self.memo.key
+                && memo.spectrogram == self.spectrogram
+                && memo.n_samples == n_samples
+            {
+                return memo.spectrogram_output.clone();
+            }
+        }
 
-        // Calculate how many points to display for 0-20Hz
-        let freq_resolution = self.sampling_frequency / fft_size as f32;
+        let samples = self.sample_signal_complex(n_samples);
+        let result = self.spectrogram.compute(&samples, &mut self.planner);
+        self.memo.spectrogram = Some(SpectrogramMemoization {
+            key: self.memo.key.clone(),
+            spectrogram: self.spectrogram,
+            n_samples,
+            spectrogram_output: result.clone(),
+        });
+        result
+    }
 
-        // Calculate magnitudes
-        let magnitudes: Vec<f32> = fft_output //[..display_points]
+    /// The two-sided spectrum of the continuous signal after the anti-aliasing filter, covering
+    /// twice the span of the replica plot.
+    ///
+    /// The signal is sampled much faster than the sampling frequency and Hann windowed, the
+    /// amplitudes are corrected for the window, so that a real tone of amplitude A shows up
+    /// as A/2 at +f and at -f.
+    fn calculate_continuous_spectrum(&mut self) -> Vec<(f32, f32)> {
+        if let Some(ref memo) = self.memo.continuous_spectrum {
+            if memo.sampling_frequency == self.sampling_frequency
+                && memo.signal == self.signal
+                && memo.window_duration == self.window_duration
+                && memo.filter == self.filter
+                && memo.periods == self.replica_plot.periods
+            {
+                return memo.spectrum_output.clone();
+            }
+        }
+
+        let (span, _) = self.replica_span();
+        let n =
+            ((4.0 * span * self.window_duration).ceil() as usize).clamp(16, MAX_CONTINUOUS_POINTS);
+        let rate = n as f32 / self.window_duration;
+        let poles = self.calculate_filter_poles();
+        let window = FftWindow {
+            kind: WindowKind::Hann,
+            ..FftWindow::default()
+        }
+        .coefficients(n);
+        let gain: f32 = window.iter().sum();
+
+        let mut buffer: Vec<Complex<f32>> = window
             .iter()
-            .map(|c| c.norm() / fft_size as f32)
-            .collect::<Vec<f32>>();
+            .enumerate()
+            .map(|(i, w)| {
+                let t = i as f32 / rate;
+                Complex::new(self.signal.value(t), self.signal.quadrature_value(t)) * w
+            })
+            .collect();
+        self.planner.plan_fft_forward(n).process(&mut buffer);
 
-        // Draw horizontal zero line
-        painter.line_segment(
-            [
-                egui::Pos2::new(rect.left(), rect.bottom()),
-                egui::Pos2::new(rect.right(), rect.bottom()),
-            ],
-            Stroke::new(1.0, Color32::YELLOW),
+        let mut result: Vec<(f32, f32)> = buffer
+            .iter()
+            .enumerate()
+            .map(|(k, c)| {
+                let freq = if k >= n / 2 {
+                    (k as f32 - n as f32) * rate / n as f32
+                } else {
+                    k as f32 * rate / n as f32
+                };
+                let response = if self.filter.enabled {
+                    self.filter.response_with(&poles, freq.abs())
+                } else {
+                    1.0
+                };
+                (freq, c.norm() / gain * response)
+            })
+            .collect();
+        result.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        self.memo.continuous_spectrum = Some(ContinuousSpectrumMemoization {
+            window_duration: self.window_duration,
+            sampling_frequency: self.sampling_frequency,
+            signal: self.signal.clone(),
+            filter: self.filter,
+            periods: self.replica_plot.periods,
+            spectrum_output: result.clone(),
+        });
+        result
+    }
+
+    /// How far the replica plot reaches on either side of 0 Hz, and whether that is less than
+    /// the requested periods.
+    ///
+    /// The continuous spectrum is calculated at twice the rate the plot needs, so that the
+    /// spectrum isn't aliased itself. Where that takes more points than allowed, the span
+    /// shrinks rather than the rate.
+    fn replica_span(&self) -> (f32, bool) {
+        let requested = (self.replica_plot.periods as f32 + 0.5) * self.sampling_frequency;
+        let budget = MAX_CONTINUOUS_POINTS as f32 / (4.0 * self.window_duration);
+        if requested > budget {
+            (budget, true)
+        } else {
+            (requested, false)
+        }
+    }
+
+    /// Draws sampling in the frequency domain: multiplying with the Dirac comb Σ δ(t - n·Ts)
+    /// convolves the spectrum with the comb Σ δ(f - k·fs), repeating it at every k·fs.
+    /// Whatever a replica puts into -fs/2..fs/2 is an alias.
+    fn render_replicas(&mut self, ui: &mut egui::Ui, plot_height: f32, plot_width: f32) {
+        let (span, capped) = self.replica_span();
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                Color32::YELLOW,
+                "Sampling in frequency: X(f) convolved with the Dirac comb Σ δ(f - k·fs) gives the replicas X(f - k·fs)",
+            );
+            if capped {
+                ui.colored_label(
+                    Color32::ORANGE,
+                    format!(
+                        "only ±{span:.0} Hz shown, {} periods of fs take more than {MAX_CONTINUOUS_POINTS} points over the window",
+                        self.replica_plot.periods
+                    ),
+                );
+            }
+        });
+        let response = ui.allocate_rect(
+            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
+            egui::Sense::hover(),
+        );
+        let rect = response.rect.intersect(ui.clip_rect());
+        let painter = ui.painter();
+
+        let spectrum = self.calculate_continuous_spectrum();
+        let fs = self.sampling_frequency;
+        // the multiples of fs on the plot
+        let periods = (span / fs) as i32;
+        let freq_to_x = |freq: f32| rect.left() + (freq + span) / (2.0 * span) * rect.width();
+
+        let full_scale = spectrum
+            .iter()
+            .map(|(_, amplitude)| *amplitude)
+            .fold(1e-6, f32::max)
+            * 1.2;
+        let amplitude_to_y =
+            |amplitude: f32| rect.bottom() - (amplitude / full_scale).min(1.0) * rect.height();
+
+        // Highlight the baseband, the only band the samples can represent, which may reach
+        // beyond a capped span
+        let baseband = egui::Rect::from_min_max(
+            egui::Pos2::new(freq_to_x(-fs / 2.0), rect.top()),
+            egui::Pos2::new(freq_to_x(fs / 2.0), rect.bottom()),
+        )
+        .intersect(rect);
+        painter.rect_filled(
+            baseband,
+            0.0,
+            Color32::from_rgba_unmultiplied(255, 255, 0, 20),
+        );
+        painter.text(
+            egui::Pos2::new(baseband.center().x, rect.top() + 10.0),
+            egui::Align2::CENTER_CENTER,
+            "baseband -fs/2..fs/2",
+            egui::FontId::proportional(12.0),
+            Color32::YELLOW,
         );
 
-        // Draw vertical axis line
         painter.line_segment(
             [
-                egui::Pos2::new(rect.left(), rect.top()),
                 egui::Pos2::new(rect.left(), rect.bottom()),
+                egui::Pos2::new(rect.right(), rect.bottom()),
             ],
             Stroke::new(1.0, Color32::YELLOW),
         );
 
-        // Draw frequency ticks every 5 Hz
-        for freq in (0..=20).step_by(5) {
-            let x_pos = rect.left() + (freq as f32 / max_display_freq) * rect.width();
-
-            // Draw tick
+        // The Dirac comb, one arrow at each multiple of fs
+        let comb_stroke = Stroke::new(1.0, Color32::GRAY);
+        for k in -periods..=periods {
+            let x = freq_to_x(k as f32 * fs);
+            let top = rect.top() + 0.1 * rect.height();
             painter.line_segment(
-                [
-                    egui::Pos2::new(x_pos, rect.bottom()),
-                    egui::Pos2::new(x_pos, rect.bottom() + 5.0),
-                ],
-                Stroke::new(1.0, Color32::YELLOW),
+                [egui::Pos2::new(x, rect.bottom()), egui::Pos2::new(x, top)],
+                comb_stroke,
             );
-
-            // Draw tick label
-            painter.text(
-                egui::Pos2::new(x_pos, rect.bottom() + 15.0),
-                egui::Align2::CENTER_CENTER,
-                format!("{freq} Hz"),
-                egui::FontId::proportional(12.0),
-                Color32::YELLOW,
+            painter.line_segment(
+                [egui::Pos2::new(x - 4.0, top + 8.0), egui::Pos2::new(x, top)],
+                comb_stroke,
             );
-        }
-
-        // Mark signal frequency position
-        let signal_freq_pos = (self.signal_frequency / max_display_freq) * rect.width();
-        if signal_freq_pos <= rect.width() {
             painter.line_segment(
-                [
-                    egui::Pos2::new(rect.left() + signal_freq_pos, rect.top()),
-                    egui::Pos2::new(rect.left() + signal_freq_pos, rect.bottom()),
-                ],
-                Stroke::new(1.0, Color32::RED),
+                [egui::Pos2::new(x + 4.0, top + 8.0), egui::Pos2::new(x, top)],
+                comb_stroke,
             );
-
+            let label = match k {
+                0 => "0".to_string(),
+                1 => "fs".to_string(),
+                -1 => "-fs".to_string(),
+                _ => format!("{k}fs"),
+            };
             painter.text(
-                egui::Pos2::new(rect.left() + signal_freq_pos, rect.top() + 15.0),
+                egui::Pos2::new(x, rect.bottom() + 15.0),
                 egui::Align2::CENTER_CENTER,
-                format!("{:.1} Hz", self.signal_frequency),
+                format!("{label} ({} Hz)", k as f32 * fs),
                 egui::FontId::proportional(12.0),
                 Color32::YELLOW,
             );
         }
 
-        // Add aliased frequency label if applicable
-        if self.signal_frequency > self.sampling_frequency / 2.0 {
-            let alias_freq = self.signal_frequency % self.sampling_frequency;
-            let alias_freq = if alias_freq > self.sampling_frequency / 2.0 {
-                self.sampling_frequency - alias_freq
-            } else {
-                alias_freq
-            };
-
-            let alias_pos = (alias_freq / max_display_freq) * rect.width();
-            if alias_pos <= rect.width() {
-                painter.line_segment(
-                    [
-                        egui::Pos2::new(rect.left() + alias_pos, rect.top()),
-                        egui::Pos2::new(rect.left() + alias_pos, rect.bottom()),
-                    ],
-                    Stroke::new(1.0, Color32::from_rgb(128, 0, 128)), // Purple
-                );
+        // The spectrum shifted by `shift`, reduced to the strongest point per pixel
+        let curve = |shift: f32| -> Vec<egui::Pos2> {
+            let first = spectrum.partition_point(|(freq, _)| freq + shift < -span);
+            let last = spectrum.partition_point(|(freq, _)| freq + shift <= span);
+            let mut points: Vec<egui::Pos2> = Vec::new();
+            for (freq, amplitude) in &spectrum[first..last] {
+                let point = egui::Pos2::new(freq_to_x(freq + shift), amplitude_to_y(*amplitude));
+                match points.last_mut() {
+                    Some(previous) if point.x - previous.x < 0.5 => {
+                        previous.y = previous.y.min(point.y);
+                    }
+                    _ => points.push(point),
+                }
+            }
+            points
+        };
 
-                painter.text(
-                    egui::Pos2::new(rect.left() + alias_pos + 50.0, rect.top() + 30.0),
-                    egui::Align2::CENTER_CENTER,
-                    format!("Alias: {alias_freq:.1} Hz"),
-                    egui::FontId::proportional(12.0),
-                    Color32::RED,
-                );
+        // The replicas, the parts falling into the baseband are drawn again as aliases.
+        // The spectrum reaches twice the span, so replicas from up to three spans away show.
+        let alias_painter = painter.with_clip_rect(baseband);
+        let max_shift = (3.0 * span / fs).ceil() as i32;
+        for k in (-max_shift..=max_shift).filter(|k| *k != 0) {
+            let points = curve(k as f32 * fs);
+            if points.len() < 2 {
+                continue;
             }
+            painter.add(egui::Shape::line(
+                points.clone(),
+                Stroke::new(1.0, Color32::from_rgb(255, 160, 60)),
+            ));
+            alias_painter.add(egui::Shape::line(points, Stroke::new(2.0, Color32::RED)));
         }
 
-        // Mark Nyquist frequency if it's in our display range
-        let nyquist_freq = self.sampling_frequency / 2.0;
-        if nyquist_freq <= max_display_freq {
-            let nyquist_pos = (nyquist_freq / max_display_freq) * rect.width();
-            painter.line_segment(
-                [
-                    egui::Pos2::new(rect.left() + nyquist_pos, rect.top()),
-                    egui::Pos2::new(rect.left() + nyquist_pos, rect.bottom()),
-                ],
-                Stroke::new(1.0, Color32::from_rgba_premultiplied(255, 255, 0, 100)),
-            );
+        // The spectrum of the continuous signal itself
+        painter.add(egui::Shape::line(
+            curve(0.0),
+            Stroke::new(1.5, Color32::LIGHT_BLUE),
+        ));
 
-            painter.text(
-                egui::Pos2::new(rect.left() + nyquist_pos, rect.bottom() - 5.0),
-                egui::Align2::CENTER_BOTTOM,
-                format!("Nyquist: {nyquist_freq:.1} Hz"),
-                egui::FontId::proportional(12.0),
-                Color32::YELLOW,
-            );
-        }
+        painter.text(
+            egui::Pos2::new(rect.right() - 5.0, rect.top() + 10.0),
+            egui::Align2::RIGHT_CENTER,
+            if self.filter.enabled {
+                "X(f) after the anti-aliasing filter"
+            } else {
+                "X(f)"
+            },
+            egui::FontId::proportional(12.0),
+            Color32::LIGHT_BLUE,
+        );
+        painter.text(
+            egui::Pos2::new(rect.right() - 5.0, rect.top() + 25.0),
+            egui::Align2::RIGHT_CENTER,
+            "X(f - k·fs), k ≠ 0",
+            egui::FontId::proportional(12.0),
+            Color32::from_rgb(255, 160, 60),
+        );
+        painter.text(
+            egui::Pos2::new(rect.right() - 5.0, rect.top() + 40.0),
+            egui::Align2::RIGHT_CENTER,
+            "aliases in the baseband",
+            egui::FontId::proportional(12.0),
+            Color32::RED,
+        );
+    }
 
-        // Draw FFT bars
-        if !magnitudes.is_empty() {
-            // For each display bucket, position it according to its frequency
-            for i_bucket in 0..magnitudes.len() {
-                // Calculate the frequency this bucket represents
-                let bucket_freq = i_bucket as f32 * freq_resolution;
+    fn render_spectrogram(&mut self, ui: &mut egui::Ui, plot_height: f32, plot_width: f32) {
+        let n_samples = self.fft_signal_size();
+        let segment_length = self.spectrogram.effective_segment_length(n_samples);
+        ui.colored_label(
+            Color32::YELLOW,
+            format!(
+                "Spectrogram (segment {segment_length} samples = {:.3} s, hop {}, {} window)",
+                segment_length as f32 / self.sampling_frequency,
+                self.spectrogram.hop,
+                self.spectrogram.window.kind.name()
+            ),
+        );
+        let response = ui.allocate_rect(
+            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
+            egui::Sense::hover(),
+        );
+        let rect = response.rect.intersect(ui.clip_rect());
+        let painter = ui.painter().with_clip_rect(rect);
+        painter.rect_filled(rect, 0.0, Color32::BLACK);
 
-                // Position the bucket according to its frequency (scaled to display width)
-                let x = rect.left() + (bucket_freq / max_display_freq) * rect.width();
+        let columns = self.calculate_spectrogram(n_samples);
+        if columns.is_empty() {
+            return;
+        }
 
-                // Calculate width based on frequency resolution
-                let next_freq = (i_bucket + 1) as f32 * freq_resolution;
-                let next_x = rect.left() + (next_freq / max_display_freq) * rect.width();
-                let bucket_width = f32::max(next_x - x, 1.0);
+        // real signals are mirrored, only 0..fs/2 is shown, complex ones cover -fs/2..fs/2
+        let complex = self.signal.is_complex();
+        let nyquist_freq = self.sampling_frequency / 2.0;
+        let min_freq = if complex { -nyquist_freq } else { 0.0 };
+        let freq_to_y = |freq: f32| {
+            rect.bottom() - (freq - min_freq) / (nyquist_freq - min_freq) * rect.height()
+        };
+        let freq_resolution = self.sampling_frequency / segment_length as f32;
 
-                let y = magnitudes[i_bucket] * 2.0 * rect.height();
+        let max_db = columns
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        // one cell per segment and bin, each segment centered on its middle sample
+        let sampling_offset = self.sampling.offset(self.sampling_frequency);
+        let hop_duration = self.spectrogram.hop.max(1) as f32 / self.sampling_frequency;
+        let mut mesh = egui::Mesh::default();
+        for (i_column, column) in columns.iter().enumerate() {
+            let t = sampling_offset
+                + (i_column * self.spectrogram.hop.max(1)) as f32 / self.sampling_frequency
+                + (segment_length - 1) as f32 / (2.0 * self.sampling_frequency);
+            let left = rect.left() + x_offset(rect, t - hop_duration / 2.0, self.window_duration);
+            let right = rect.left() + x_offset(rect, t + hop_duration / 2.0, self.window_duration);
+            if right < rect.left() || left > rect.right() {
+                continue;
+            }
 
-                painter.rect_filled(
+            for (k, db) in column.iter().enumerate() {
+                let freq = if complex && k >= segment_length / 2 {
+                    (k as f32 - segment_length as f32) * freq_resolution
+                } else if k <= segment_length / 2 {
+                    k as f32 * freq_resolution
+                } else {
+                    continue;
+                };
+                let value = 1.0 + (db - max_db) / self.spectrogram.dynamic_range_db;
+                mesh.add_colored_rect(
                     egui::Rect::from_min_max(
-                        egui::Pos2::new(x, rect.bottom() - y),
-                        egui::Pos2::new(x + bucket_width * 0.9, rect.bottom()),
+                        egui::Pos2::new(left, freq_to_y(freq + freq_resolution / 2.0)),
+                        egui::Pos2::new(right, freq_to_y(freq - freq_resolution / 2.0)),
                     ),
-                    0.0,
-                    Color32::LIGHT_BLUE,
+                    colormap(value),
                 );
             }
         }
+        painter.add(egui::Shape::mesh(mesh));
 
-        // draw_axis_labels(painter, rect, "Frequency (Hz)", "Magnitude");
+        // Label the frequency axis
+        let mut labels = vec![(0.0, "0 Hz".to_string())];
+        labels.push((nyquist_freq, format!("fs/2 = {nyquist_freq:.1} Hz")));
+        if complex {
+            labels.push((-nyquist_freq, format!("-fs/2 = {:.1} Hz", -nyquist_freq)));
+        }
+        for (freq, label) in labels {
+            let y = freq_to_y(freq).clamp(rect.top() + 8.0, rect.bottom() - 8.0);
+            painter.text(
+                egui::Pos2::new(rect.left() + 5.0, y),
+                egui::Align2::LEFT_CENTER,
+                label,
+                egui::FontId::proportional(12.0),
+                Color32::WHITE,
+            );
+        }
+        painter.text(
+            egui::Pos2::new(rect.right() - 5.0, rect.top() + 8.0),
+            egui::Align2::RIGHT_CENTER,
+            format!(
+                "{max_db:.0} .. {:.0} dB",
+                max_db - self.spectrogram.dynamic_range_db
+            ),
+            egui::FontId::proportional(12.0),
+            Color32::WHITE,
+        );
     }
 }
 
 impl AliasApp {
-    fn render_fft_raw_values(
-        &mut self,
-        ctx: &egui::Context,
-        ui: &mut egui::Ui,
-        fft_output: &Vec<Complex<f32>>,
-    ) {
-        let fft_size = fft_output.len();
-        let freq_resolution = self.sampling_frequency / fft_size as f32;
-
-        egui::Window::new("Raw FFT Values")
-            .open(&mut self.show_raw_fft_values)
-            .resizable(true)
-            .default_width(400.0)
-            .max_height(600.0)
-            .show(ctx, |ui| {
-                ui.label(format!("FFT Size: {}", fft_size));
-                ui.label(format!("Frequency Resolution: {:.4} Hz", freq_resolution));
-                ui.separator();
+    /// The first `n_samples` samples after the rate change.
+    fn calculate_resampled(&mut self, n_samples: usize) -> Vec<Complex<f32>> {
+        if let Some(ref memo) = self.memo.resampled {
+            if memo.key == self.memo.key
+                && memo.multirate == self.multirate
+                && memo.n_samples == n_samples
+            {
+                return memo.output.clone();
+            }
+        }
 
-                egui::ScrollArea::vertical()
-                    .max_height(500.0)
-                    .show(ui, |ui| {
-                        ui.horizontal(|ui| {
-                            ui.label("Bin");
-                            ui.separator();
-                            ui.label("Frequency (Hz)");
-                            ui.separator();
-                            ui.label("Real");
-                            ui.separator();
-                            ui.label("Imaginary");
-                            ui.separator();
-                            ui.label("Magnitude / N");
-                            ui.separator();
-                            ui.label("Phase (rad) / π");
-                        });
-                        ui.separator();
+        let result = self.multirate.apply(&self.sample_signal_complex(n_samples));
+        self.memo.resampled = Some(MultirateMemoization {
+            key: self.memo.key.clone(),
+            multirate: self.multirate,
+            n_samples,
+            output: result.clone(),
+        });
+        result
+    }
 
-                        for (i, complex_val) in fft_output.iter().enumerate() {
-                            let freq = i as f32 * freq_resolution;
-                            let magnitude = complex_val.norm() / fft_size as f32;
-                            let phase = complex_val.arg() / PI;
+    /// The spectrum of the rate changed FFT input, its length is the number of output samples.
+    fn calculate_resampled_fft(&mut self, n_samples: usize) -> Vec<Complex<f32>> {
+        if let Some(ref memo) = self.memo.resampled_fft {
+            if memo.key == self.memo.key
+                && memo.multirate == self.multirate
+                && memo.n_samples == n_samples
+            {
+                return memo.output.clone();
+            }
+        }
 
-                            ui.horizontal(|ui| {
-                                ui.label(format!("{:4}", i));
-                                ui.separator();
-                                ui.label(format!("{:8.3}", freq));
-                                ui.separator();
-                                ui.label(format!("{:10.6}", complex_val.re));
-                                ui.separator();
-                                ui.label(format!("{:10.6}", complex_val.im));
-                                ui.separator();
-                                ui.label(format!("{:10.6}", magnitude));
-                                ui.separator();
-                                ui.label(format!("{:8.3}", phase));
-                            });
-                        }
-                    });
-            });
+        let mut result = self.multirate.apply(&self.sample_signal_complex(n_samples));
+        if !result.is_empty() {
+            let fft = self.planner.plan_fft_forward(result.len());
+            fft.process(&mut result);
+        }
+        self.memo.resampled_fft = Some(MultirateMemoization {
+            key: self.memo.key.clone(),
+            multirate: self.multirate,
+            n_samples,
+            output: result.clone(),
+        });
+        result
     }
-}
 
-impl AliasApp {
-    fn render_reconstructed(
+    /// Plots the samples after the rate change, and their spectrum.
+    fn render_resampled(
         &mut self,
         ui: &mut egui::Ui,
-        signal: Vec<(f32, f32)>,
+        horizontal_pixels: u32,
         plot_height: f32,
         plot_width: f32,
-        draw_axis_labels: impl Fn(&egui::Painter, egui::Rect, &str, &str),
-        recon_signal: Vec<(f32, f32)>,
+        y_range: (f32, f32),
     ) {
-        ui.colored_label(Color32::YELLOW, format!("Reconstructed signal"));
-        let response4 = ui.allocate_rect(
+        let output_rate = self.multirate.output_rate(self.sampling_frequency);
+        ui.colored_label(
+            Color32::YELLOW,
+            format!(
+                "Resampled by {}/{} ({}): {output_rate:.2} Hz",
+                self.multirate.up,
+                self.multirate.down,
+                self.multirate.filter.name()
+            ),
+        );
+        let response = ui.allocate_rect(
             egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
             egui::Sense::hover(),
         );
-
-        let rect = response4.rect.intersect(ui.clip_rect());
+        let rect = response.rect.intersect(ui.clip_rect());
         let painter = ui.painter();
 
         // Draw horizontal zero line
         painter.line_segment(
             [
-                egui::Pos2::new(rect.left(), rect.top() + rect.height() / 2.0),
-                egui::Pos2::new(rect.right(), rect.top() + rect.height() / 2.0),
+                egui::Pos2::new(rect.left(), rect.top() + y_offset(rect, 0.0, y_range)),
+                egui::Pos2::new(rect.right(), rect.top() + y_offset(rect, 0.0, y_range)),
             ],
             Stroke::new(1.0, Color32::YELLOW),
         );
 
-        // Draw reconstructed signal
-        for i in 0..recon_signal.len() - 1 {
-            let (x1, y1) = recon_signal[i];
-            let (x2, y2) = recon_signal[i + 1];
-
-            painter.line_segment(
-                [
-                    rect.left_top()
-                        + vec2(
-                            x1 / (2.0 * PI) * rect.width(),
-                            rect.height() / 2.0 - y1 * (rect.height() / 2.0),
-                        ),
-                    rect.left_top()
-                        + vec2(
-                            x2 / (2.0 * PI) * rect.width(),
-                            rect.height() / 2.0 - y2 * (rect.height() / 2.0),
-                        ),
-                ],
-                Stroke::new(4.0, Color32::RED),
-            );
-        }
-
-        // Draw original signal for comparison (thinner line)
-        for i in 0..signal.len() - 1 {
-            let (x1, y1) = signal[i];
-            let (x2, y2) = signal[i + 1];
-            painter.line_segment(
-                [
-                    rect.left_top()
-                        + vec2(
-                            x1 / (2.0 * PI) * rect.width(),
-                            rect.height() / 2.0 - y1 * (rect.height() / 2.0),
-                        ),
-                    rect.left_top()
-                        + vec2(
-                            x2 / (2.0 * PI) * rect.width(),
-                            rect.height() / 2.0 - y2 * (rect.height() / 2.0),
-                        ),
-                ],
-                Stroke::new(1.0, Color32::GREEN),
-            );
-        }
-
-        // Draw sample points
-        let sample_points = self.calculate_sample_points();
-        for (x, y) in &sample_points {
-            painter.circle_filled(
+        // Draw the original signal for comparison
+        let signal = self.calculate_signal(horizontal_pixels);
+        let points = signal
+            .iter()
+            .map(|(t, y)| {
                 rect.left_top()
                     + vec2(
-                        *x / (2.0 * PI) * rect.width(),
-                        rect.height() / 2.0 - y * (rect.height() / 2.0),
-                    ),
-                4.0,
-                Color32::GREEN,
-            );
+                        x_offset(rect, *t, self.window_duration),
+                        y_offset(rect, *y, y_range),
+                    )
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            Stroke::new(1.0, Color32::from_rgba_premultiplied(0, 120, 0, 120)),
+        ));
+
+        // Draw the resampled sequence as stems, output sample m is taken at m / fs' after the first
+        let n_input = self.window_sample_count();
+        let sampling_offset = self.sampling.offset(self.sampling_frequency);
+        let resampled = self.calculate_resampled(n_input);
+        let complex = self.signal.is_complex();
+        for (m, y) in resampled.iter().enumerate() {
+            let t = sampling_offset + m as f32 / output_rate;
+            if t > self.window_duration {
+                break;
+            }
+            let parts = if complex {
+                vec![(y.re, Color32::RED), (y.im, Color32::from_rgb(0, 170, 255))]
+            } else {
+                vec![(y.re, Color32::RED)]
+            };
+            for (value, color) in parts {
+                let x = rect.left() + x_offset(rect, t, self.window_duration);
+                painter.line_segment(
+                    [
+                        egui::Pos2::new(x, rect.top() + y_offset(rect, 0.0, y_range)),
+                        egui::Pos2::new(x, rect.top() + y_offset(rect, value, y_range)),
+                    ],
+                    Stroke::new(1.0, color),
+                );
+                painter.circle_filled(
+                    egui::Pos2::new(x, rect.top() + y_offset(rect, value, y_range)),
+                    3.0,
+                    color,
+                );
+            }
         }
 
-        // draw_axis_labels(painter, rect, "Time", "Amplitude");
-
-        // Add legend
-        painter.rect_filled(
-            egui::Rect::from_min_max(
-                egui::Pos2::new(rect.right() - 160.0, rect.top() + 10.0),
-                egui::Pos2::new(rect.right() - 10.0, rect.top() + 70.0),
-            ),
-            3.0,
-            Color32::from_rgba_premultiplied(40, 40, 40, 200),
+        ui.add_space(5.0);
+        ui.colored_label(Color32::YELLOW, "Resampled spectrum");
+        let response = ui.allocate_rect(
+            egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::new(plot_width, plot_height)),
+            egui::Sense::hover(),
         );
+        let rect = response.rect.intersect(ui.clip_rect());
+        let painter = ui.painter();
 
-        painter.line_segment(
-            [
-                egui::Pos2::new(rect.right() - 150.0, rect.top() + 20.0),
-                egui::Pos2::new(rect.right() - 130.0, rect.top() + 20.0),
-            ],
-            Stroke::new(1.0, Color32::GREEN),
-        );
+        let fft_output = self.calculate_resampled_fft(self.fft_signal_size());
+        if fft_output.is_empty() {
+            return;
+        }
+        let fft_size = fft_output.len();
+        let freq_resolution = output_rate / fft_size as f32;
+
+        // the bins cover 0..fs', shown on the frequency axis of the input rate
+        let max_display_freq = self.sampling_frequency.max(output_rate).max(20.0);
+        let freq_to_x = |freq: f32| rect.left() + freq / max_display_freq * rect.width();
+
+        let magnitudes: Vec<f32> = fft_output
+            .iter()
+            .map(|c| c.norm() / fft_size as f32)
+            .collect();
+        let bar_scale = |i_bucket: usize| if complex || i_bucket == 0 { 1.0 } else { 2.0 };
+        let full_scale = magnitudes
+            .iter()
+            .enumerate()
+            .map(|(i, m)| m * bar_scale(i))
+            .fold(1.0, f32::max);
 
         painter.line_segment(
             [
-                egui::Pos2::new(rect.right() - 150.0, rect.top() + 40.0),
-                egui::Pos2::new(rect.right() - 130.0, rect.top() + 40.0),
+                egui::Pos2::new(rect.left(), rect.bottom()),
+                egui::Pos2::new(rect.right(), rect.bottom()),
             ],
-            Stroke::new(4.0, Color32::RED),
-        );
-
-        painter.circle_filled(
-            egui::Pos2::new(rect.right() - 140.0, rect.top() + 60.0),
-            4.0,
-            Color32::GREEN,
+            Stroke::new(1.0, Color32::YELLOW),
         );
 
-        painter.text(
-            egui::Pos2::new(rect.right() - 120.0, rect.top() + 20.0),
-            egui::Align2::LEFT_CENTER,
-            "Original Signal",
-            egui::FontId::proportional(12.0),
-            Color32::YELLOW,
-        );
+        let tick_step = tick_step(max_display_freq, 4);
+        for i_tick in 0..=(max_display_freq / tick_step) as i32 {
+            let freq = i_tick as f32 * tick_step;
+            let x_pos = freq_to_x(freq);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(x_pos, rect.bottom()),
+                    egui::Pos2::new(x_pos, rect.bottom() + 5.0),
+                ],
+                Stroke::new(1.0, Color32::YELLOW),
+            );
+            painter.text(
+                egui::Pos2::new(x_pos, rect.bottom() + 15.0),
+                egui::Align2::CENTER_CENTER,
+                format!("{freq} Hz"),
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+        }
 
-        painter.text(
-            egui::Pos2::new(rect.right() - 120.0, rect.top() + 40.0),
-            egui::Align2::LEFT_CENTER,
-            "Reconstructed",
-            egui::FontId::proportional(12.0),
-            Color32::YELLOW,
-        );
+        for (i_bucket, magnitude) in magnitudes.iter().enumerate() {
+            let x = freq_to_x(i_bucket as f32 * freq_resolution);
+            let bucket_width =
+                f32::max(freq_to_x((i_bucket + 1) as f32 * freq_resolution) - x, 1.0);
+            let y = magnitude * bar_scale(i_bucket) / full_scale * rect.height();
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::Pos2::new(x, rect.bottom() - y),
+                    egui::Pos2::new(x + bucket_width * 0.9, rect.bottom()),
+                ),
+                0.0,
+                Color32::LIGHT_BLUE,
+            );
+        }
 
-        painter.text(
-            egui::Pos2::new(rect.right() - 120.0, rect.top() + 60.0),
-            egui::Align2::LEFT_CENTER,
-            "Sample Points",
-            egui::FontId::proportional(12.0),
-            Color32::YELLOW,
-        );
+        // Mark the Nyquist frequencies before and after the rate change
+        for (freq, label, color) in [
+            (self.sampling_frequency / 2.0, "fs/2", Color32::GRAY),
+            (output_rate / 2.0, "fs'/2", Color32::from_rgb(128, 0, 128)),
+        ] {
+            let x = freq_to_x(freq);
+            painter.line_segment(
+                [
+                    egui::Pos2::new(x, rect.top()),
+                    egui::Pos2::new(x, rect.bottom()),
+                ],
+                Stroke::new(1.0, color),
+            );
+            painter.text(
+                egui::Pos2::new(x + 5.0, rect.top() + 15.0),
+                egui::Align2::LEFT_CENTER,
+                format!("{label}: {freq:.2} Hz"),
+                egui::FontId::proportional(12.0),
+                Color32::YELLOW,
+            );
+        }
     }
 }
 
 impl AliasApp {
+    /// How much the anti-aliasing filter has to attenuate the aliases for them to no longer count:
+    /// below the quantization noise with the ADC enabled, 40 dB otherwise.
+    fn sufficient_attenuation_db(&self) -> f32 {
+        if self.adc.enabled {
+            self.adc.theoretical_sqnr_db()
+        } else {
+            40.0
+        }
+    }
+
     fn render_aliasing_warning(&mut self, ui: &mut egui::Ui) {
-        let alias_freq = self.signal_frequency % self.sampling_frequency;
-        let alias_freq = if alias_freq > self.sampling_frequency / 2.0 {
-            self.sampling_frequency - alias_freq
+        let nyquist_freq = self.sampling_frequency / 2.0;
+        let mut lines = Vec::new();
+        // the least the anti-aliasing filter attenuates any of the aliases
+        let mut min_attenuation = f32::INFINITY;
+        let poles = self.calculate_filter_poles();
+        let mut attenuated = |frequency: f32| {
+            if !self.filter.enabled {
+                return String::new();
+            }
+            let attenuation = -20.0 * self.filter.response_with(&poles, frequency).log10();
+            min_attenuation = min_attenuation.min(attenuation);
+            format!(", attenuated by {attenuation:.1} dB")
+        };
+        if self.signal.kind == SignalKind::Chirp {
+            let (f_min, f_max) = self.signal.chirp.frequency_range();
+            let aliased_ranges =
+                alias_range(nyquist_freq.max(f_min), f_max, self.sampling_frequency)
+                    .iter()
+                    .map(|(low, high)| format!("{low:.1} .. {high:.1} Hz"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+            lines.push(format!(
+                "Chirp sweeps {f_min:.1} .. {f_max:.1} Hz, {:.0}% of the sweep is above Nyquist ({nyquist_freq:.1} Hz)",
+                100.0 * self.signal.chirp.fraction_above(nyquist_freq)
+            ));
+            lines.push(format!(
+                "The part above {:.1} Hz appears as: {aliased_ranges}{}",
+                nyquist_freq.max(f_min),
+                attenuated(nyquist_freq.max(f_min))
+            ));
+        }
+        if self.signal.kind == SignalKind::Bandpass {
+            let bandpass = &self.signal.bandpass;
+            let line = match bandpass.nyquist_zone(self.sampling_frequency) {
+                Some(zone) => format!(
+                    "The band lies entirely in Nyquist zone {zone}, undersampling keeps it intact{}",
+                    if bandpass.is_inverted(self.sampling_frequency) {
+                        " but inverts its spectrum"
+                    } else {
+                        ""
+                    }
+                ),
+                None => "The band crosses a Nyquist zone boundary, its folded halves overlap"
+                    .to_string(),
+            };
+            lines.push(line + &attenuated(bandpass.center_frequency));
+        }
+        if let (SignalKind::Wav, Some(wav)) = (self.signal.kind, &self.signal.wav.wav) {
+            lines.push(format!(
+                "The WAV file is sampled at {} Hz and may contain frequencies up to {:.1} Hz (Nyquist: {nyquist_freq:.1} Hz){}",
+                wav.sample_rate,
+                wav.sample_rate as f32 / 2.0,
+                attenuated(nyquist_freq)
+            ));
+        }
+        for line in self.signal.spectral_lines() {
+            if self.is_aliased(line.frequency) {
+                let alias_freq = self.apparent_frequency(line.frequency);
+                lines.push(format!(
+                    "{}: {:.1} Hz appears as: {alias_freq:.1} Hz (Nyquist: {nyquist_freq:.1} Hz){}",
+                    line.label.unwrap_or("Signal"),
+                    line.frequency,
+                    attenuated(line.frequency.abs())
+                ));
+            }
+        }
+        for c in self.signal.active_components() {
+            if c.frequency < nyquist_freq && c.max_frequency(self.signal.band_limit) >= nyquist_freq
+            {
+                // the lowest harmonic above Nyquist is attenuated the least
+                let first_aliased_harmonic =
+                    ((nyquist_freq / c.frequency).floor() + 1.0) * c.frequency;
+                lines.push(format!(
+                    "{} {:.1} Hz: harmonics above {nyquist_freq:.1} Hz fold back below Nyquist{}",
+                    c.waveform.name(),
+                    c.frequency,
+                    attenuated(first_aliased_harmonic)
+                ));
+            }
+        }
+
+        let (title, background, color) = if min_attenuation.is_finite()
+            && min_attenuation >= self.sufficient_attenuation_db()
+        {
+            (
+                format!(
+                    "Aliases attenuated by at least {min_attenuation:.1} dB by the anti-aliasing filter."
+                ),
+                Color32::from_rgba_premultiplied(0, 60, 0, 200),
+                Color32::LIGHT_GREEN,
+            )
         } else {
-            alias_freq
+            (
+                "Aliasing detected! Signal frequency exceeds Nyquist limit.".to_string(),
+                Color32::from_rgba_premultiplied(100, 0, 0, 200),
+                Color32::RED,
+            )
         };
 
         ui.horizontal(|ui| {
@@ -1141,38 +4511,34 @@ impl AliasApp {
                 let warning_rect = ui.allocate_rect(
                     egui::Rect::from_min_size(
                         ui.cursor().min,
-                        egui::Vec2::new(ui.available_width() - 20.0, 60.0),
+                        egui::Vec2::new(
+                            ui.available_width() - 20.0,
+                            40.0 + 20.0 * lines.len() as f32,
+                        ),
                     ),
                     egui::Sense::hover(),
                 );
 
                 let rect = warning_rect.rect;
-                ui.painter().rect_filled(
-                    rect,
-                    5.0,
-                    Color32::from_rgba_premultiplied(100, 0, 0, 200),
-                );
+                ui.painter().rect_filled(rect, 5.0, background);
 
                 ui.painter().text(
                     egui::Pos2::new(rect.left() + 20.0, rect.top() + 20.0),
                     egui::Align2::LEFT_CENTER,
-                    "Aliasing detected! Signal frequency exceeds Nyquist limit.",
+                    title,
                     egui::FontId::proportional(16.0),
-                    Color32::RED,
+                    color,
                 );
 
-                ui.painter().text(
-                    egui::Pos2::new(rect.left() + 20.0, rect.top() + 40.0),
-                    egui::Align2::LEFT_CENTER,
-                    format!(
-                        "Signal: {:.1} Hz appears as: {:.1} Hz (Nyquist: {:.1} Hz)",
-                        self.signal_frequency,
-                        alias_freq,
-                        self.sampling_frequency / 2.0
-                    ),
-                    egui::FontId::proportional(14.0),
-                    Color32::LIGHT_RED,
-                );
+                for (i, line) in lines.iter().enumerate() {
+                    ui.painter().text(
+                        egui::Pos2::new(rect.left() + 20.0, rect.top() + 40.0 + 20.0 * i as f32),
+                        egui::Align2::LEFT_CENTER,
+                        line,
+                        egui::FontId::proportional(14.0),
+                        Color32::LIGHT_RED,
+                    );
+                }
             });
         });
     }
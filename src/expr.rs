@@ -0,0 +1,390 @@
+//! A small math expression language for user-defined signals, e.g.
+//! `sin(2*pi*3*t) + 0.3*sign(sin(2*pi*7*t))`.
+//!
+//! Expressions are parsed once and compiled into a postfix program, which is cheap to
+//! evaluate for every pixel and sample.
+
+use std::f32::consts::{E, PI};
+
+use crate::reconstruction::sinc;
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Const(f32),
+    Time,
+    Neg,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Func1(fn(f32) -> f32),
+    Func2(fn(f32, f32) -> f32),
+}
+
+fn sign(x: f32) -> f32 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+fn lookup_func1(name: &str) -> Option<fn(f32) -> f32> {
+    Some(match name {
+        "sin" => f32::sin,
+        "cos" => f32::cos,
+        "tan" => f32::tan,
+        "asin" => f32::asin,
+        "acos" => f32::acos,
+        "atan" => f32::atan,
+        "sinh" => f32::sinh,
+        "cosh" => f32::cosh,
+        "tanh" => f32::tanh,
+        "abs" => f32::abs,
+        "sqrt" => f32::sqrt,
+        "exp" => f32::exp,
+        "ln" => f32::ln,
+        "log" => f32::log10,
+        "floor" => f32::floor,
+        "ceil" => f32::ceil,
+        "round" => f32::round,
+        "fract" => f32::fract,
+        "sign" => sign,
+        "sinc" => sinc,
+        _ => return None,
+    })
+}
+
+fn lookup_func2(name: &str) -> Option<fn(f32, f32) -> f32> {
+    Some(match name {
+        "min" => f32::min,
+        "max" => f32::max,
+        "pow" => f32::powf,
+        "atan2" => f32::atan2,
+        "mod" => f32::rem_euclid,
+        _ => return None,
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f32),
+    Ident(String),
+    Symbol(char),
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // exponent, e.g. 1e-3
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let value = literal
+                .parse()
+                .map_err(|_| format!("invalid number '{literal}' at {}", start + 1))?;
+            tokens.push((start, Token::Number(value)));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((start, Token::Ident(chars[start..i].iter().collect())));
+        } else if "+-*/^(),".contains(c) {
+            // `**` is an alias for `^`
+            if c == '*' && chars.get(i + 1) == Some(&'*') {
+                tokens.push((i, Token::Symbol('^')));
+                i += 2;
+            } else {
+                tokens.push((i, Token::Symbol(c)));
+                i += 1;
+            }
+        } else {
+            return Err(format!("unexpected '{c}' at {}", i + 1));
+        }
+    }
+    Ok(tokens)
+}
+
+/// How deeply an expression may nest, so that parsing can't overflow the stack
+const MAX_DEPTH: usize = 64;
+/// How many values evaluating an expression may keep at once
+const MAX_STACK: usize = 64;
+
+/// Recursive descent parser emitting postfix ops.
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    /// the length of the input, to report errors at its end
+    len: usize,
+    /// how many [`Self::unary`] calls are nested, every level of nesting goes through it
+    depth: usize,
+    ops: Vec<Op>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn error(&self, message: &str) -> String {
+        match self.tokens.get(self.position) {
+            Some((at, _)) => format!("{message} at {}", at + 1),
+            None => format!("{message} at {}", self.len + 1),
+        }
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), String> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{symbol}'")))
+        }
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<(), String> {
+        self.term()?;
+        loop {
+            if self.eat('+') {
+                self.term()?;
+                self.ops.push(Op::Add);
+            } else if self.eat('-') {
+                self.term()?;
+                self.ops.push(Op::Sub);
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<(), String> {
+        self.unary()?;
+        loop {
+            if self.eat('*') {
+                self.unary()?;
+                self.ops.push(Op::Mul);
+            } else if self.eat('/') {
+                self.unary()?;
+                self.ops.push(Op::Div);
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    // unary := ('-' | '+') unary | power
+    fn unary(&mut self) -> Result<(), String> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("expression nested too deeply"));
+        }
+        self.depth += 1;
+        let result = if self.eat('-') {
+            self.unary().map(|()| self.ops.push(Op::Neg))
+        } else if self.eat('+') {
+            self.unary()
+        } else {
+            self.power()
+        };
+        self.depth -= 1;
+        result
+    }
+
+    // power := primary ('^' unary)?, right associative so that 2^3^2 = 2^9
+    fn power(&mut self) -> Result<(), String> {
+        self.primary()?;
+        if self.eat('^') {
+            self.unary()?;
+            self.ops.push(Op::Pow);
+        }
+        Ok(())
+    }
+
+    // primary := number | constant | 't' | function '(' args ')' | '(' expr ')'
+    fn primary(&mut self) -> Result<(), String> {
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.position += 1;
+                self.ops.push(Op::Const(value));
+                Ok(())
+            }
+            Some(Token::Ident(name)) => {
+                self.position += 1;
+                match name.as_str() {
+                    "t" => self.ops.push(Op::Time),
+                    "pi" => self.ops.push(Op::Const(PI)),
+                    "e" => self.ops.push(Op::Const(E)),
+                    _ => {
+                        if let Some(f) = lookup_func1(&name) {
+                            self.expect('(')?;
+                            self.expr()?;
+                            self.expect(')')?;
+                            self.ops.push(Op::Func1(f));
+                        } else if let Some(f) = lookup_func2(&name) {
+                            self.expect('(')?;
+                            self.expr()?;
+                            self.expect(',')?;
+                            self.expr()?;
+                            self.expect(')')?;
+                            self.ops.push(Op::Func2(f));
+                        } else {
+                            self.position -= 1;
+                            return Err(self.error(&format!("unknown name '{name}'")));
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Some(Token::Symbol('(')) => {
+                self.position += 1;
+                self.expr()?;
+                self.expect(')')
+            }
+            Some(_) => Err(self.error("unexpected symbol")),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+}
+
+/// A compiled expression in the variable `t` (in seconds).
+#[derive(Clone, Debug)]
+pub struct Expression {
+    text: String,
+    program: Result<Vec<Op>, String>,
+}
+
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        // the program is derived from the text
+        self.text == other.text
+    }
+}
+
+impl Expression {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            program: Self::compile(text),
+        }
+    }
+
+    fn compile(text: &str) -> Result<Vec<Op>, String> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            len: text.chars().count(),
+            depth: 0,
+            ops: Vec::new(),
+        };
+        parser.expr()?;
+        if parser.position < parser.tokens.len() {
+            return Err(parser.error("unexpected symbol"));
+        }
+
+        // the evaluation stack has a fixed size, check that the program fits
+        let mut stack_len = 0;
+        for op in &parser.ops {
+            match op {
+                Op::Const(_) | Op::Time => stack_len += 1,
+                Op::Neg | Op::Func1(_) => {}
+                _ => stack_len -= 1,
+            }
+            if stack_len > MAX_STACK {
+                return Err("expression nested too deeply".to_string());
+            }
+        }
+        Ok(parser.ops)
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Why the expression couldn't be compiled, if it couldn't.
+    pub fn error(&self) -> Option<&str> {
+        self.program.as_ref().err().map(|e| e.as_str())
+    }
+
+    /// Evaluates the expression at time `t`, an invalid expression is constant 0.
+    pub fn value(&self, t: f32) -> f32 {
+        let Ok(ops) = &self.program else {
+            return 0.0;
+        };
+
+        // evaluated for every pixel and sample, so the stack lives on the stack,
+        // `compile` made sure the program fits
+        let mut stack = [0.0f32; MAX_STACK];
+        let mut len = 0;
+        for op in ops {
+            match *op {
+                Op::Const(value) => {
+                    stack[len] = value;
+                    len += 1;
+                }
+                Op::Time => {
+                    stack[len] = t;
+                    len += 1;
+                }
+                Op::Func1(f) => stack[len - 1] = f(stack[len - 1]),
+                Op::Neg => stack[len - 1] = -stack[len - 1],
+                _ => {
+                    len -= 1;
+                    let (a, b) = (stack[len - 1], stack[len]);
+                    stack[len - 1] = match *op {
+                        Op::Add => a + b,
+                        Op::Sub => a - b,
+                        Op::Mul => a * b,
+                        Op::Div => a / b,
+                        Op::Pow => a.powf(b),
+                        Op::Func2(f) => f(a, b),
+                        _ => unreachable!(),
+                    };
+                }
+            }
+        }
+
+        let y = if len > 0 { stack[len - 1] } else { 0.0 };
+        // keep NaN / inf (e.g. from sqrt(-1) or 1/0) from poisoning the FFT
+        if y.is_finite() { y } else { 0.0 }
+    }
+}
+
+impl Default for Expression {
+    fn default() -> Self {
+        Self::new("sin(2*pi*3*t) + 0.3*sign(sin(2*pi*7*t))")
+    }
+}
@@ -0,0 +1,153 @@
+use std::path::{Path, PathBuf};
+
+/// The app's own directory on the external storage, set on startup on Android. Unlike the
+/// rest of the shared storage it can be read without a storage permission, and files can be
+/// copied into it over USB.
+#[cfg(target_os = "android")]
+pub static ANDROID_FILES_DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Where the browser starts, the app's own directory on Android and the working directory
+/// elsewhere.
+fn start_directory() -> PathBuf {
+    #[cfg(target_os = "android")]
+    if let Some(directory) = ANDROID_FILES_DIR.get() {
+        return directory.clone();
+    }
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/"))
+}
+
+/// A file picker drawn with egui, for the targets without a native file dialog.
+/// Lists the subdirectories and the files with the given extension.
+pub struct FileBrowser {
+    pub open: bool,
+    extension: &'static str,
+    directory: PathBuf,
+    /// (name, is directory), directories first
+    entries: Vec<(String, bool)>,
+    error: Option<String>,
+}
+
+impl FileBrowser {
+    pub fn new(extension: &'static str) -> Self {
+        Self {
+            open: false,
+            extension,
+            directory: start_directory(),
+            entries: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Opens the browser, in the directory of `path` if that exists.
+    pub fn open_at(&mut self, path: &str) {
+        let path = Path::new(path);
+        let directory = if path.is_dir() {
+            Some(path)
+        } else {
+            path.parent().filter(|parent| parent.is_dir())
+        };
+        if let Some(directory) = directory {
+            self.directory = directory.to_path_buf();
+        }
+        self.open = true;
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.entries.clear();
+        match std::fs::read_dir(&self.directory) {
+            Ok(read_dir) => {
+                self.error = None;
+                for entry in read_dir.flatten() {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    let is_dir = entry.path().is_dir();
+                    let matches = Path::new(&name)
+                        .extension()
+                        .is_some_and(|e| e.eq_ignore_ascii_case(self.extension));
+                    if !name.starts_with('.') && (is_dir || matches) {
+                        self.entries.push((name, is_dir));
+                    }
+                }
+                self.entries.sort_by(|(a, a_dir), (b, b_dir)| {
+                    b_dir
+                        .cmp(a_dir)
+                        .then_with(|| a.to_lowercase().cmp(&b.to_lowercase()))
+                });
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+
+    /// Shows the browser while it is open, returns the file the user picked.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<PathBuf> {
+        if !self.open {
+            return None;
+        }
+
+        let mut picked = None;
+        let mut navigate_to = None;
+        let mut open = self.open;
+        egui::Window::new(format!("Open .{} file", self.extension))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let parent = self.directory.parent();
+                    if ui
+                        .add_enabled(parent.is_some(), egui::Button::new("up"))
+                        .clicked()
+                    {
+                        navigate_to = parent.map(Path::to_path_buf);
+                    }
+                    ui.label(self.directory.to_string_lossy());
+                });
+                #[cfg(target_os = "android")]
+                if let Some(directory) = ANDROID_FILES_DIR.get() {
+                    ui.label(format!(
+                        "Copy .{} files into {} to open them here.",
+                        self.extension,
+                        directory.to_string_lossy()
+                    ));
+                }
+                ui.separator();
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for (name, is_dir) in &self.entries {
+                            // full width rows, so that they are easy to hit on a touch screen
+                            let text = if *is_dir {
+                                format!("{name}/")
+                            } else {
+                                name.clone()
+                            };
+                            let button = egui::Button::new(text)
+                                .min_size(egui::vec2(ui.available_width(), 28.0));
+                            if ui.add(button).clicked() {
+                                let path = self.directory.join(name);
+                                if *is_dir {
+                                    navigate_to = Some(path);
+                                } else {
+                                    picked = Some(path);
+                                }
+                            }
+                        }
+                        if self.entries.is_empty() && self.error.is_none() {
+                            ui.label(format!("no folders or .{} files here", self.extension));
+                        }
+                    });
+            });
+
+        if let Some(directory) = navigate_to {
+            self.directory = directory;
+            self.refresh();
+        }
+        self.open = open && picked.is_none();
+        picked
+    }
+}
@@ -0,0 +1,277 @@
+use rustfft::num_complex::Complex;
+use std::f64::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterKind {
+    Butterworth,
+    Chebyshev,
+    Bessel,
+}
+
+impl FilterKind {
+    pub const ALL: [FilterKind; 3] = [
+        FilterKind::Butterworth,
+        FilterKind::Chebyshev,
+        FilterKind::Bessel,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FilterKind::Butterworth => "Butterworth",
+            FilterKind::Chebyshev => "Chebyshev (type I)",
+            FilterKind::Bessel => "Bessel",
+        }
+    }
+}
+
+/// How much finer than the sampling grid the analog filter is simulated
+const OVERSAMPLING: f32 = 16.0;
+/// The filter starts this many periods of the cutoff frequency before the first sample,
+/// so that its transient has decayed
+const SETTLING_PERIODS: f32 = 20.0;
+/// Limits the cost of simulating the filter, the grid gets coarser beyond it
+const MAX_STEPS: f32 = 4_000_000.0;
+/// The coarsest grid relative to the cutoff frequency, where the transition band is still
+/// far enough below the Nyquist frequency of the grid to keep its shape
+const MIN_CUTOFF_OVERSAMPLING: f32 = 16.0;
+
+/// An analog low-pass in front of the sampler.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AntiAliasFilter {
+    pub enabled: bool,
+    pub kind: FilterKind,
+    pub order: u32,
+    /// in Hz, the -3 dB point, or the edge of the ripple band for Chebyshev
+    pub cutoff: f32,
+    /// Chebyshev only, the passband ripple in dB
+    pub ripple_db: f32,
+}
+
+impl Default for AntiAliasFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: FilterKind::Butterworth,
+            order: 4,
+            cutoff: 4.0,
+            ripple_db: 1.0,
+        }
+    }
+}
+
+/// A second (or first) order section, `b` and `a` are the coefficients of z^0, z^-1, z^-2,
+/// normalized to `a[0] = 1`.
+struct Section {
+    b: [f64; 3],
+    a: [f64; 3],
+}
+
+impl AntiAliasFilter {
+    /// The poles of the transfer function in rad/s.
+    ///
+    /// Finding them is expensive for the Bessel filter, so they are found once and passed
+    /// to [`Self::response_with`].
+    pub fn poles(&self) -> Vec<Complex<f64>> {
+        let n = self.order.max(1) as usize;
+        let omega_c = 2.0 * PI * self.cutoff as f64;
+        match self.kind {
+            FilterKind::Butterworth => (0..n)
+                .map(|k| {
+                    let theta = PI * (2 * k + n + 1) as f64 / (2 * n) as f64;
+                    Complex::from_polar(omega_c, theta)
+                })
+                .collect(),
+            FilterKind::Chebyshev => {
+                let epsilon = self.ripple_epsilon();
+                let mu = (1.0 / epsilon).asinh() / n as f64;
+                (0..n)
+                    .map(|k| {
+                        let theta = PI * (2 * k + 1) as f64 / (2 * n) as f64;
+                        Complex::new(-mu.sinh() * theta.sin(), mu.cosh() * theta.cos()) * omega_c
+                    })
+                    .collect()
+            }
+            FilterKind::Bessel => {
+                let poles = bessel_poles(n);
+                let omega_3db = cutoff_3db(&poles);
+                poles
+                    .into_iter()
+                    .map(|p| p * (omega_c / omega_3db))
+                    .collect()
+            }
+        }
+    }
+
+    fn ripple_epsilon(&self) -> f64 {
+        (10f64.powf(self.ripple_db as f64 / 10.0) - 1.0).sqrt()
+    }
+
+    /// The gain at DC, a Chebyshev filter of even order starts at the bottom of its ripple.
+    fn dc_gain(&self) -> f64 {
+        if self.kind == FilterKind::Chebyshev && self.order % 2 == 0 {
+            1.0 / (1.0 + self.ripple_epsilon().powi(2)).sqrt()
+        } else {
+            1.0
+        }
+    }
+
+    /// The magnitude of the frequency response at `frequency` in Hz, given the `poles` of
+    /// this filter from [`Self::poles`].
+    pub fn response_with(&self, poles: &[Complex<f64>], frequency: f32) -> f32 {
+        let s = Complex::new(0.0, 2.0 * PI * frequency as f64);
+        let h = poles.iter().map(|p| -p / (s - p)).product::<Complex<f64>>();
+        (self.dc_gain() * h.norm()) as f32
+    }
+
+    /// The filter discretized with the bilinear transform at `step_frequency`.
+    ///
+    /// The transform compresses the frequency axis towards the Nyquist frequency of the grid,
+    /// it is prewarped so that the cutoff stays where it is.
+    fn sections(&self, step_frequency: f64) -> Vec<Section> {
+        let omega_c = 2.0 * PI * self.cutoff as f64;
+        let k = omega_c / (omega_c / (2.0 * step_frequency)).tan();
+        let mut sections: Vec<Section> = self
+            .poles()
+            .into_iter()
+            // each conjugate pair becomes one section
+            .filter(|p| p.im >= -1e-9 * p.norm())
+            .map(|p| {
+                if p.im.abs() <= 1e-9 * p.norm() {
+                    // H(s) = c / (s + c)
+                    let c = -p.re;
+                    let a0 = k + c;
+                    Section {
+                        b: [c / a0, c / a0, 0.0],
+                        a: [1.0, (c - k) / a0, 0.0],
+                    }
+                } else {
+                    // H(s) = w^2 / (s^2 + a s + w^2)
+                    let a = -2.0 * p.re;
+                    let w2 = p.norm_sqr();
+                    let a0 = k * k + a * k + w2;
+                    Section {
+                        b: [w2 / a0, 2.0 * w2 / a0, w2 / a0],
+                        a: [
+                            1.0,
+                            (2.0 * w2 - 2.0 * k * k) / a0,
+                            (k * k - a * k + w2) / a0,
+                        ],
+                    }
+                }
+            })
+            .collect();
+        if let Some(first) = sections.first_mut() {
+            for b in first.b.iter_mut() {
+                *b *= self.dc_gain();
+            }
+        }
+        sections
+    }
+
+    /// Filters the continuous signal `value` and returns the output at the instants `times`
+    /// (in seconds).
+    ///
+    /// The filter is simulated on a grid finer than both the sampling and the highest frequency
+    /// of the signal, starting early enough that it has settled by the first instant.
+    pub fn apply(
+        &self,
+        value: impl Fn(f32) -> f32,
+        times: &[f32],
+        sampling_frequency: f32,
+        max_frequency: f32,
+    ) -> Vec<f32> {
+        if !self.enabled || times.is_empty() {
+            return times.iter().map(|t| value(*t)).collect();
+        }
+
+        let first = times.iter().copied().fold(f32::INFINITY, f32::min);
+        let last = times.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let start = first - SETTLING_PERIODS / self.cutoff;
+        let step_frequency = (OVERSAMPLING
+            * sampling_frequency.max(self.cutoff).max(max_frequency))
+        .min(MAX_STEPS / (last - start))
+        .max(MIN_CUTOFF_OVERSAMPLING * self.cutoff);
+        let dt = 1.0 / step_frequency;
+        let n_steps = ((last - start) / dt).ceil() as usize + 2;
+
+        let mut output: Vec<f64> = (0..n_steps)
+            .map(|i| value(start + i as f32 * dt) as f64)
+            .collect();
+        for section in self.sections(step_frequency as f64) {
+            // transposed direct form II
+            let (mut z1, mut z2) = (0.0, 0.0);
+            for y in output.iter_mut() {
+                let x = *y;
+                *y = section.b[0] * x + z1;
+                z1 = section.b[1] * x - section.a[1] * *y + z2;
+                z2 = section.b[2] * x - section.a[2] * *y;
+            }
+        }
+
+        // interpolate between the grid points
+        times
+            .iter()
+            .map(|t| {
+                let position = (t - start) / dt;
+                let i = (position.floor() as usize).min(n_steps - 2);
+                let fraction = (position - i as f32) as f64;
+                (output[i] + (output[i + 1] - output[i]) * fraction) as f32
+            })
+            .collect()
+    }
+}
+
+/// The poles of the Bessel filter of order `n`, the roots of the reverse Bessel polynomial,
+/// found with the Durand-Kerner method.
+fn bessel_poles(n: usize) -> Vec<Complex<f64>> {
+    // the coefficients of s^k, (2n - k)! / (2^(n - k) * k! * (n - k)!), the one of s^n is 1
+    let factorial = |k: usize| (1..=k).map(|i| i as f64).product::<f64>();
+    let coefficients: Vec<f64> = (0..=n)
+        .map(|k| {
+            factorial(2 * n - k) / (2f64.powi((n - k) as i32) * factorial(k) * factorial(n - k))
+        })
+        .collect();
+    let polynomial = |s: Complex<f64>| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Complex::new(0.0, 0.0), |acc, c| acc * s + c)
+    };
+
+    let mut roots: Vec<Complex<f64>> = (0..n)
+        .map(|k| Complex::new(0.4, 0.9).powu(k as u32 + 1))
+        .collect();
+    for _ in 0..500 {
+        for i in 0..n {
+            let others: Complex<f64> = (0..n)
+                .filter(|j| *j != i)
+                .map(|j| roots[i] - roots[j])
+                .product();
+            let correction = polynomial(roots[i]) / others;
+            roots[i] -= correction;
+        }
+    }
+    roots
+}
+
+/// The frequency in rad/s where the all-pole filter with unity DC gain drops by 3 dB.
+fn cutoff_3db(poles: &[Complex<f64>]) -> f64 {
+    let magnitude = |omega: f64| {
+        let s = Complex::new(0.0, omega);
+        poles
+            .iter()
+            .map(|p| -p / (s - p))
+            .product::<Complex<f64>>()
+            .norm()
+    };
+    let (mut low, mut high) = (0.0, 100.0);
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if magnitude(middle) > 0.5f64.sqrt() {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
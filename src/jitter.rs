@@ -0,0 +1,78 @@
+use std::f32::consts::PI;
+
+use crate::rng::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JitterKind {
+    Gaussian,
+    Uniform,
+    Sinusoidal,
+}
+
+impl JitterKind {
+    pub const ALL: [JitterKind; 3] = [
+        JitterKind::Gaussian,
+        JitterKind::Uniform,
+        JitterKind::Sinusoidal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            JitterKind::Gaussian => "Random (Gaussian)",
+            JitterKind::Uniform => "Random (uniform)",
+            JitterKind::Sinusoidal => "Sinusoidal",
+        }
+    }
+}
+
+/// Jitter of the sampling clock, displacing each sampling instant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Jitter {
+    pub enabled: bool,
+    pub kind: JitterKind,
+    /// RMS of the timing error in seconds
+    pub rms: f32,
+    /// sinusoidal jitter only, the frequency the timing error oscillates with in Hz
+    pub frequency: f32,
+    pub seed: u64,
+}
+
+impl Default for Jitter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: JitterKind::Gaussian,
+            rms: 0.001,
+            frequency: 1.3,
+            seed: 1,
+        }
+    }
+}
+
+impl Jitter {
+    /// Displaces the instants (in seconds) the samples are taken at,
+    /// sample `i` always has the same timing error regardless of the number of samples.
+    pub fn apply(&self, sample_times: &mut [f32]) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut rng = Rng::new(self.seed);
+        for t in sample_times.iter_mut() {
+            let error = match self.kind {
+                JitterKind::Gaussian => rng.gaussian(),
+                // uniform in [-√3, √3] has a variance of 1
+                JitterKind::Uniform => (2.0 * rng.uniform() - 1.0) * 3f32.sqrt(),
+                // a sine with an amplitude of √2 has an RMS of 1
+                JitterKind::Sinusoidal => 2f32.sqrt() * (2.0 * PI * self.frequency * *t).sin(),
+            };
+            *t += self.rms * error;
+        }
+    }
+
+    /// The SNR in dB a full-scale sine at `frequency` is limited to by the jitter,
+    /// `-20 * log10(2π * f * σ)`.
+    pub fn snr_limit_db(&self, frequency: f32) -> f32 {
+        -20.0 * (2.0 * PI * frequency * self.rms).log10()
+    }
+}
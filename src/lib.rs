@@ -1,5 +1,20 @@
+pub mod adc;
 pub mod app;
+pub mod expr;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod file_browser;
+pub mod filter;
 pub mod fonts;
+pub mod jitter;
+pub mod multirate;
+pub mod noise;
+pub mod reconstruction;
+pub mod rng;
+pub mod sampling;
+pub mod signal;
+pub mod spectrogram;
+pub mod wav;
+pub mod window;
 
 #[cfg(any(target_os = "android", target_os = "ios"))]
 pub mod lib_mobile;
@@ -268,6 +268,12 @@ fn android_main(app: AndroidApp) {
         android_logger::Config::default().with_max_level(log::LevelFilter::Warn),
     );
 
+    // the WAV file browser starts in the app's own directory, which needs no permission
+    if let Some(directory) = app.external_data_path() {
+        let _ = std::fs::create_dir_all(&directory);
+        let _ = crate::file_browser::ANDROID_FILES_DIR.set(directory);
+    }
+
     let event_loop = EventLoop::<UserEvent>::with_user_event()
         .with_android_app(app)
         .build()
@@ -1,10 +1,25 @@
 #![warn(clippy::all, rust_2018_idioms)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+mod adc;
 mod app;
+mod expr;
+#[cfg(not(target_arch = "wasm32"))]
+mod file_browser;
+mod filter;
 #[cfg(target_arch = "wasm32")]
 mod font_wasm;
 mod fonts;
+mod jitter;
+mod multirate;
+mod noise;
+mod reconstruction;
+mod rng;
+mod sampling;
+mod signal;
+mod spectrogram;
+mod wav;
+mod window;
 
 use egui::{FontData, FontDefinitions, FontFamily};
 
@@ -28,6 +43,7 @@ fn main() -> eframe::Result {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 300.0])
             .with_min_inner_size([300.0, 220.0])
+            .with_drag_and_drop(true)
             .with_icon(
                 // NOTE: Adding an icon is optional
                 eframe::icon_data::from_png_bytes(&include_bytes!("../assets/icon-256.png")[..])
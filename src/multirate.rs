@@ -0,0 +1,134 @@
+use rustfft::num_complex::Complex;
+
+use crate::reconstruction::sinc;
+use crate::window::{FftWindow, WindowKind};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResamplingFilter {
+    None,
+    WindowedSinc,
+    Cic,
+}
+
+impl ResamplingFilter {
+    pub const ALL: [ResamplingFilter; 3] = [
+        ResamplingFilter::None,
+        ResamplingFilter::WindowedSinc,
+        ResamplingFilter::Cic,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResamplingFilter::None => "No filter",
+            ResamplingFilter::WindowedSinc => "Windowed-sinc FIR",
+            ResamplingFilter::Cic => "CIC",
+        }
+    }
+}
+
+/// A rate change by L/M after sampling: upsampling by `up`, filtering, then downsampling by `down`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Multirate {
+    pub enabled: bool,
+    /// L, the interpolation factor
+    pub up: u32,
+    /// M, the decimation factor
+    pub down: u32,
+    pub filter: ResamplingFilter,
+    /// windowed-sinc only, how many zero crossings of the sinc on either side are kept
+    pub sinc_zero_crossings: u32,
+    /// CIC only, the number of integrator / comb stages
+    pub cic_stages: u32,
+}
+
+impl Default for Multirate {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            up: 1,
+            down: 2,
+            filter: ResamplingFilter::None,
+            sinc_zero_crossings: 8,
+            cic_stages: 3,
+        }
+    }
+}
+
+impl Multirate {
+    /// The sampling frequency after the rate change.
+    pub fn output_rate(&self, sampling_frequency: f32) -> f32 {
+        sampling_frequency * self.up as f32 / self.down as f32
+    }
+
+    /// The impulse response at the upsampled rate, centered on its middle tap so that the
+    /// output isn't delayed, with a DC gain of `up` to make up for the inserted zeros.
+    fn impulse_response(&self) -> Vec<f32> {
+        let up = self.up.max(1) as usize;
+        let rate_change = self.up.max(self.down).max(1) as usize;
+        let taps = match self.filter {
+            // only the zero stuffing and the dropped samples, with their images and aliases
+            ResamplingFilter::None => vec![1.0],
+            // cuts off at the lower of the two Nyquist frequencies, Blackman windowed
+            ResamplingFilter::WindowedSinc => {
+                let half = (self.sinc_zero_crossings.max(1) as usize * rate_change) as i32;
+                let window = FftWindow {
+                    kind: WindowKind::Blackman,
+                    ..FftWindow::default()
+                }
+                .coefficients((2 * half + 1) as usize);
+                (-half..=half)
+                    .zip(window)
+                    .map(|(i, window)| sinc(i as f32 / rate_change as f32) * window)
+                    .collect()
+            }
+            // the integrator / comb cascade is equivalent to a boxcar of the rate change,
+            // convolved with itself once per stage
+            ResamplingFilter::Cic => {
+                let boxcar = vec![1.0; rate_change];
+                (1..self.cic_stages.max(1)).fold(boxcar.clone(), |h, _| {
+                    let mut convolved = vec![0.0; h.len() + boxcar.len() - 1];
+                    for (i, a) in h.iter().enumerate() {
+                        for (j, b) in boxcar.iter().enumerate() {
+                            convolved[i + j] += a * b;
+                        }
+                    }
+                    convolved
+                })
+            }
+        };
+        if self.filter == ResamplingFilter::None {
+            return taps;
+        }
+        let gain = up as f32 / taps.iter().sum::<f32>();
+        taps.into_iter().map(|h| h * gain).collect()
+    }
+
+    /// Changes the rate of `samples` by L/M.
+    ///
+    /// Polyphase: of the upsampled sequence only every L-th value is non-zero, so each output
+    /// sample only evaluates the taps that meet one of the input samples.
+    pub fn apply(&self, samples: &[Complex<f32>]) -> Vec<Complex<f32>> {
+        let up = self.up.max(1) as i64;
+        let down = self.down.max(1) as i64;
+        let h = self.impulse_response();
+        let center = (h.len() / 2) as i64;
+
+        let n_output = (samples.len() as i64 * up + down - 1) / down;
+        (0..n_output)
+            .map(|m| {
+                // the position of the output sample on the upsampled grid
+                let position = m * down;
+                // the input samples i whose tap position - i * up + center lies within h
+                let first = (position + center - (h.len() as i64 - 1) + up - 1)
+                    .div_euclid(up)
+                    .max(0);
+                let last = (position + center)
+                    .div_euclid(up)
+                    .min(samples.len() as i64 - 1);
+                (first..=last)
+                    .map(|i| samples[i as usize] * h[(position - i * up + center) as usize])
+                    .sum()
+            })
+            .collect()
+    }
+}
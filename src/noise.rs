@@ -0,0 +1,95 @@
+use crate::rng::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoiseKind {
+    Gaussian,
+    Uniform,
+    Pink,
+}
+
+impl NoiseKind {
+    pub const ALL: [NoiseKind; 3] = [NoiseKind::Gaussian, NoiseKind::Uniform, NoiseKind::Pink];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NoiseKind::Gaussian => "White (Gaussian)",
+            NoiseKind::Uniform => "White (uniform)",
+            NoiseKind::Pink => "Pink",
+        }
+    }
+}
+
+/// Additive noise on the sampled signal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Noise {
+    pub enabled: bool,
+    pub kind: NoiseKind,
+    /// signal to noise ratio in dB, relative to the AC power of the signal
+    pub snr_db: f32,
+    pub seed: u64,
+}
+
+impl Default for Noise {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: NoiseKind::Gaussian,
+            snr_db: 20.0,
+            seed: 1,
+        }
+    }
+}
+
+/// RMS of Paul Kellet's pink noise filter when driven by unit white noise
+const PINK_RMS: f32 = 3.04;
+/// The pink noise filter starts at rest, its slowest pole (0.99886) has a time constant of
+/// about 900 steps. It runs this long before the first sample, so that it starts settled.
+const PINK_SETTLING_STEPS: usize = 5000;
+
+impl Noise {
+    /// The RMS value of the noise for a signal with the given power.
+    pub fn rms(&self, signal_power: f32) -> f32 {
+        (signal_power / 10f32.powf(self.snr_db / 10.0)).sqrt()
+    }
+
+    /// Adds noise to `samples`, sample `i` always receives the same noise value
+    /// regardless of the length of `samples`.
+    pub fn apply(&self, samples: &mut [f32], signal_power: f32) {
+        if !self.enabled {
+            return;
+        }
+
+        let rms = self.rms(signal_power);
+        let mut rng = Rng::new(self.seed);
+        // state of the pink noise filter
+        let mut b = [0.0f32; 7];
+        // Paul Kellet's refined method, see https://www.firstpr.com.au/dsp/pink-noise/
+        let mut pink = |rng: &mut Rng| {
+            let white = rng.gaussian();
+            b[0] = 0.99886 * b[0] + white * 0.0555179;
+            b[1] = 0.99332 * b[1] + white * 0.0750759;
+            b[2] = 0.96900 * b[2] + white * 0.153852;
+            b[3] = 0.86650 * b[3] + white * 0.3104856;
+            b[4] = 0.55000 * b[4] + white * 0.5329522;
+            b[5] = -0.7616 * b[5] - white * 0.0168980;
+            let pink = b.iter().sum::<f32>() + white * 0.5362;
+            b[6] = white * 0.115926;
+            pink / PINK_RMS
+        };
+        if self.kind == NoiseKind::Pink {
+            for _ in 0..PINK_SETTLING_STEPS {
+                pink(&mut rng);
+            }
+        }
+
+        for y in samples.iter_mut() {
+            let n = match self.kind {
+                NoiseKind::Gaussian => rng.gaussian(),
+                // uniform in [-√3, √3] has a variance of 1
+                NoiseKind::Uniform => (2.0 * rng.uniform() - 1.0) * 3f32.sqrt(),
+                NoiseKind::Pink => pink(&mut rng),
+            };
+            *y += rms * n;
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use rustfft::num_complex::Complex;
+use std::f32::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReconstructionMethod {
+    Fft,
+    Sinc,
+    ZeroOrderHold,
+    Linear,
+    CubicSpline,
+}
+
+impl ReconstructionMethod {
+    pub const ALL: [ReconstructionMethod; 5] = [
+        ReconstructionMethod::Fft,
+        ReconstructionMethod::Sinc,
+        ReconstructionMethod::ZeroOrderHold,
+        ReconstructionMethod::Linear,
+        ReconstructionMethod::CubicSpline,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReconstructionMethod::Fft => "FFT bins",
+            ReconstructionMethod::Sinc => "Ideal sinc (Whittaker–Shannon)",
+            ReconstructionMethod::ZeroOrderHold => "Zero-order hold (DAC)",
+            ReconstructionMethod::Linear => "First-order hold (linear)",
+            ReconstructionMethod::CubicSpline => "Cubic spline",
+        }
+    }
+}
+
+/// How the continuous signal is rebuilt from its samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reconstruction {
+    pub method: ReconstructionMethod,
+    /// sinc only, how many samples on either side of `t` contribute
+    pub sinc_taps: u32,
+}
+
+impl Default for Reconstruction {
+    fn default() -> Self {
+        Self {
+            method: ReconstructionMethod::Fft,
+            sinc_taps: 8,
+        }
+    }
+}
+
+/// The normalized sinc, `sin(πx) / (πx)`.
+pub fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+impl Reconstruction {
+    /// Interpolates the samples `values` taken at the ascending instants `times` (in seconds)
+    /// at each instant of `at`.
+    ///
+    /// The FFT method needs the spectrum and isn't handled here, it returns zeros.
+    pub fn reconstruct(
+        &self,
+        times: &[f32],
+        values: &[Complex<f32>],
+        sampling_frequency: f32,
+        at: &[f32],
+    ) -> Vec<Complex<f32>> {
+        let zero = Complex::new(0.0, 0.0);
+        if times.is_empty() {
+            return vec![zero; at.len()];
+        }
+
+        // the spline can't pass through two samples at the same instant, such as user placed
+        // ones dragged onto each other, so those are merged
+        let merged;
+        let (times, values) = if self.method == ReconstructionMethod::CubicSpline {
+            merged = merge_coincident(times, values);
+            (&merged.0[..], &merged.1[..])
+        } else {
+            (times, values)
+        };
+        let spline = if self.method == ReconstructionMethod::CubicSpline {
+            spline_second_derivatives(times, values)
+        } else {
+            Vec::new()
+        };
+
+        at.iter()
+            .map(|&t| {
+                // the last sample taken at or before t
+                let k = times.partition_point(|&t_k| t_k <= t);
+                match self.method {
+                    ReconstructionMethod::Fft => zero,
+                    // x(t) = sum_k [ x_k * sinc(fs * (t - t_k)) ], truncated to the nearest taps
+                    ReconstructionMethod::Sinc => {
+                        let taps = self.sinc_taps as usize;
+                        let first = k.saturating_sub(taps);
+                        let last = (k + taps).min(times.len());
+                        (first..last)
+                            .map(|i| values[i] * sinc(sampling_frequency * (t - times[i])))
+                            .sum()
+                    }
+                    // hold each sample until the next one, nothing has been output before the first
+                    ReconstructionMethod::ZeroOrderHold => {
+                        if k == 0 {
+                            zero
+                        } else {
+                            values[k - 1]
+                        }
+                    }
+                    ReconstructionMethod::Linear => {
+                        // outside of the samples, hold the nearest one
+                        if k == 0 {
+                            values[0]
+                        } else if k == times.len() {
+                            values[k - 1]
+                        } else {
+                            let fraction = (t - times[k - 1]) / (times[k] - times[k - 1]);
+                            values[k - 1] + (values[k] - values[k - 1]) * fraction
+                        }
+                    }
+                    ReconstructionMethod::CubicSpline => {
+                        // outside of the samples, hold the nearest one
+                        if k == 0 {
+                            values[0]
+                        } else if k == times.len() {
+                            values[k - 1]
+                        } else {
+                            let h = times[k] - times[k - 1];
+                            let a = (times[k] - t) / h;
+                            let b = (t - times[k - 1]) / h;
+                            values[k - 1] * a
+                                + values[k] * b
+                                + (spline[k - 1] * (a * a * a - a) + spline[k] * (b * b * b - b))
+                                    * (h * h / 6.0)
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Replaces the samples at practically the same instant by their mean, so that all
+/// intervals between the ascending `times` have a length.
+fn merge_coincident(times: &[f32], values: &[Complex<f32>]) -> (Vec<f32>, Vec<Complex<f32>>) {
+    let span = times[times.len() - 1] - times[0];
+    let tolerance = 1e-6 * span.max(f32::MIN_POSITIVE);
+    let mut merged_times: Vec<f32> = Vec::with_capacity(times.len());
+    let mut merged_values: Vec<Complex<f32>> = Vec::with_capacity(values.len());
+    // how many samples the last merged one is the mean of
+    let mut count = 0.0;
+    for (t, value) in times.iter().zip(values) {
+        match (merged_times.last(), merged_values.last_mut()) {
+            (Some(last), Some(mean)) if t - last <= tolerance => {
+                count += 1.0;
+                *mean += (value - *mean) / count;
+            }
+            _ => {
+                merged_times.push(*t);
+                merged_values.push(*value);
+                count = 1.0;
+            }
+        }
+    }
+    (merged_times, merged_values)
+}
+
+/// The second derivatives of the natural cubic spline through the samples,
+/// solving the tridiagonal system with the Thomas algorithm.
+fn spline_second_derivatives(times: &[f32], values: &[Complex<f32>]) -> Vec<Complex<f32>> {
+    let n = times.len();
+    let zero = Complex::new(0.0, 0.0);
+    let mut second = vec![zero; n];
+    if n < 3 {
+        return second;
+    }
+
+    // forward sweep, the natural spline has second derivatives of 0 at both ends
+    let mut diagonal = vec![0.0; n];
+    let mut rhs = vec![zero; n];
+    for i in 1..n - 1 {
+        let h_left = times[i] - times[i - 1];
+        let h_right = times[i + 1] - times[i];
+        let slope_change =
+            (values[i + 1] - values[i]) / h_right - (values[i] - values[i - 1]) / h_left;
+        diagonal[i] = 2.0 * (h_left + h_right);
+        rhs[i] = slope_change * 6.0;
+        if i > 1 {
+            let factor = h_left / diagonal[i - 1];
+            diagonal[i] -= factor * h_left;
+            rhs[i] = rhs[i] - rhs[i - 1] * factor;
+        }
+    }
+
+    // back substitution
+    for i in (1..n - 1).rev() {
+        let h_right = times[i + 1] - times[i];
+        second[i] = (rhs[i] - second[i + 1] * h_right) / diagonal[i];
+    }
+    second
+}
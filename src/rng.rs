@@ -0,0 +1,36 @@
+use std::f32::consts::PI;
+
+/// A small, seedable pseudo random number generator (SplitMix64).
+///
+/// Everything random in the app goes through this, so that a given seed always
+/// produces the same figures.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed in `[0, 1)`.
+    pub fn uniform(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Normally distributed with mean 0 and standard deviation 1 (Box-Muller).
+    pub fn gaussian(&mut self) -> f32 {
+        let u1 = 1.0 - self.uniform(); // (0, 1], so that ln() stays finite
+        let u2 = self.uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+}
@@ -0,0 +1,124 @@
+use crate::rng::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SamplingMode {
+    Uniform,
+    Random,
+    AdditiveRandom,
+    UserPlaced,
+}
+
+impl SamplingMode {
+    pub const ALL: [SamplingMode; 4] = [
+        SamplingMode::Uniform,
+        SamplingMode::Random,
+        SamplingMode::AdditiveRandom,
+        SamplingMode::UserPlaced,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SamplingMode::Uniform => "Uniform",
+            SamplingMode::Random => "Random (one per period)",
+            SamplingMode::AdditiveRandom => "Additive random",
+            SamplingMode::UserPlaced => "User placed",
+        }
+    }
+}
+
+/// When the samples are taken.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sampling {
+    pub mode: SamplingMode,
+    /// additive random only, how much an interval may deviate from the sampling period,
+    /// as a fraction of it
+    pub spread: f32,
+    pub seed: u64,
+    /// the phase of the sampling clock, delaying every sample by this fraction of the sampling
+    /// period, not applied to user placed samples which are positioned directly
+    pub phase: f32,
+    /// whether `phase` is in seconds instead, so that the delay stays the same when the
+    /// sampling frequency changes
+    pub phase_in_seconds: bool,
+    /// user placed only, the sample times (in seconds) within one period of `pattern_period`
+    pub user_times: Vec<f32>,
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Self {
+            mode: SamplingMode::Uniform,
+            spread: 0.5,
+            seed: 1,
+            phase: 0.0,
+            phase_in_seconds: false,
+            user_times: Vec::new(),
+        }
+    }
+}
+
+impl Sampling {
+    /// The instants (in seconds) the first `n_samples` samples are taken at,
+    /// with an average distance of 1.0 / sampling_frequency.
+    ///
+    /// Sample `i` is always taken at the same instant regardless of `n_samples`.
+    /// The first sample is taken at `phase` sampling periods, or seconds.
+    /// User placed samples repeat every `pattern_period` seconds.
+    pub fn sample_times(
+        &self,
+        n_samples: usize,
+        sampling_frequency: f32,
+        pattern_period: f32,
+    ) -> Vec<f32> {
+        let period = 1.0 / sampling_frequency;
+        let offset = self.offset(sampling_frequency);
+        let mut rng = Rng::new(self.seed);
+        match self.mode {
+            SamplingMode::Uniform => (0..n_samples).map(|i| offset + i as f32 * period).collect(),
+            // one sample at a random instant within each sampling period
+            SamplingMode::Random => (0..n_samples)
+                .map(|i| offset + (i as f32 + rng.uniform()) * period)
+                .collect(),
+            // the intervals between samples are random, so the errors accumulate
+            SamplingMode::AdditiveRandom => {
+                let mut t = offset;
+                (0..n_samples)
+                    .map(|_| {
+                        let sample_t = t;
+                        t += period * (1.0 + self.spread * (2.0 * rng.uniform() - 1.0));
+                        sample_t
+                    })
+                    .collect()
+            }
+            SamplingMode::UserPlaced => {
+                if self.user_times.is_empty() {
+                    return Vec::new();
+                }
+                let n_user = self.user_times.len();
+                (0..n_samples)
+                    .map(|i| self.user_times[i % n_user] + (i / n_user) as f32 * pattern_period)
+                    .collect()
+            }
+        }
+    }
+
+    /// The delay of the first sample in seconds.
+    pub fn offset(&self, sampling_frequency: f32) -> f32 {
+        if self.mode == SamplingMode::UserPlaced {
+            0.0
+        } else if self.phase_in_seconds {
+            self.phase
+        } else {
+            self.phase / sampling_frequency
+        }
+    }
+
+    /// Places the user samples on the uniform grid of `sampling_frequency` within `pattern_period`,
+    /// as a starting point for dragging them around.
+    pub fn reset_user_times(&mut self, sampling_frequency: f32, pattern_period: f32) {
+        let n_samples = ((pattern_period * sampling_frequency) as usize).max(1);
+        self.user_times = (0..n_samples)
+            .map(|i| i as f32 / sampling_frequency)
+            .collect();
+    }
+}
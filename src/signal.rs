@@ -0,0 +1,718 @@
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+use crate::expr::Expression;
+use crate::wav::Wav;
+
+/// The shape of one period of a signal component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Sawtooth,
+    Triangle,
+    Pulse,
+}
+
+impl Waveform {
+    pub const ALL: [Waveform; 5] = [
+        Waveform::Sine,
+        Waveform::Square,
+        Waveform::Sawtooth,
+        Waveform::Triangle,
+        Waveform::Pulse,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Waveform::Sine => "Sine",
+            Waveform::Square => "Square",
+            Waveform::Sawtooth => "Sawtooth",
+            Waveform::Triangle => "Triangle",
+            Waveform::Pulse => "Pulse",
+        }
+    }
+
+    /// Evaluates the (band-unlimited) waveform at phase `theta` (in rad).
+    pub fn value(&self, theta: f32, duty_cycle: f32) -> f32 {
+        let x = (theta / (2.0 * PI)).rem_euclid(1.0);
+        match self {
+            Waveform::Sine => theta.sin(),
+            Waveform::Square => {
+                if x < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Sawtooth => 2.0 * (x + 0.5).fract() - 1.0,
+            Waveform::Triangle => 2.0 / PI * theta.sin().asin(),
+            Waveform::Pulse => {
+                if x < duty_cycle {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// The mean value of one period.
+    pub fn dc(&self, duty_cycle: f32) -> f32 {
+        match self {
+            Waveform::Pulse => duty_cycle,
+            _ => 0.0,
+        }
+    }
+
+    /// Amplitude and phase of the `k`-th harmonic (k >= 1),
+    /// so that the waveform is `dc + Σ a_k·sin(k·θ + φ_k)`.
+    pub fn harmonic(&self, k: u32, duty_cycle: f32) -> (f32, f32) {
+        let kf = k as f32;
+        match self {
+            Waveform::Sine => (if k == 1 { 1.0 } else { 0.0 }, 0.0),
+            Waveform::Square => (if k % 2 == 1 { 4.0 / (PI * kf) } else { 0.0 }, 0.0),
+            Waveform::Sawtooth => {
+                let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+                (sign * 2.0 / (PI * kf), 0.0)
+            }
+            Waveform::Triangle => {
+                if k % 2 == 1 {
+                    let sign = if (k / 2) % 2 == 0 { 1.0 } else { -1.0 };
+                    (sign * 8.0 / (PI * PI * kf * kf), 0.0)
+                } else {
+                    (0.0, 0.0)
+                }
+            }
+            Waveform::Pulse => (
+                2.0 * (PI * kf * duty_cycle).sin() / (PI * kf),
+                PI / 2.0 - PI * kf * duty_cycle,
+            ),
+        }
+    }
+
+    /// Evaluates the waveform from its harmonics `1..=n_harmonics` only.
+    pub fn band_limited_value(&self, theta: f32, duty_cycle: f32, n_harmonics: u32) -> f32 {
+        let mut y = self.dc(duty_cycle);
+        for k in 1..=n_harmonics {
+            let (amplitude, phase) = self.harmonic(k, duty_cycle);
+            y += amplitude * (k as f32 * theta + phase).sin();
+        }
+        y
+    }
+}
+
+/// A single periodic component of the input signal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SignalComponent {
+    pub waveform: Waveform,
+    /// only used by [`Waveform::Pulse`], between 0 and 1
+    pub duty_cycle: f32,
+    /// drop all harmonics at or above [`Signal::band_limit`]
+    pub band_limited: bool,
+    pub frequency: f32,
+    pub amplitude: f32,
+    /// the phase of the component, between 0 and 2 (must be multiplied with π)
+    pub phase: f32,
+}
+
+impl SignalComponent {
+    pub fn value(&self, t: f32, band_limit: f32) -> f32 {
+        let theta = self.frequency * 2.0 * PI * t + self.phase * PI;
+        let y = if self.band_limited {
+            self.waveform.band_limited_value(
+                theta,
+                self.duty_cycle,
+                self.harmonic_count(band_limit),
+            )
+        } else {
+            self.waveform.value(theta, self.duty_cycle)
+        };
+        self.amplitude * y
+    }
+
+    /// The number of harmonics below `band_limit`.
+    pub fn harmonic_count(&self, band_limit: f32) -> u32 {
+        ((band_limit / self.frequency).ceil() as u32).saturating_sub(1)
+    }
+
+    /// The highest frequency contained in this component.
+    pub fn max_frequency(&self, band_limit: f32) -> f32 {
+        match (self.waveform, self.band_limited) {
+            (_, true) => self.harmonic_count(band_limit) as f32 * self.frequency,
+            (Waveform::Sine, false) => self.frequency,
+            (_, false) => f32::INFINITY,
+        }
+    }
+}
+
+impl Default for SignalComponent {
+    fn default() -> Self {
+        Self {
+            waveform: Waveform::Sine,
+            duty_cycle: 0.25,
+            band_limited: false,
+            frequency: 3.0,
+            amplitude: 1.0,
+            phase: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChirpSweep {
+    Linear,
+    Exponential,
+}
+
+impl ChirpSweep {
+    pub const ALL: [ChirpSweep; 2] = [ChirpSweep::Linear, ChirpSweep::Exponential];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChirpSweep::Linear => "Linear",
+            ChirpSweep::Exponential => "Exponential",
+        }
+    }
+}
+
+/// A sine sweeping from `start_frequency` to `end_frequency` within `duration`,
+/// after which the sweep starts over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chirp {
+    pub sweep: ChirpSweep,
+    pub start_frequency: f32,
+    pub end_frequency: f32,
+    /// the sweep time in seconds, this covers the displayed window
+    pub duration: f32,
+}
+
+impl Default for Chirp {
+    fn default() -> Self {
+        Self {
+            sweep: ChirpSweep::Linear,
+            start_frequency: 1.0,
+            end_frequency: 15.0,
+            duration: 1.0,
+        }
+    }
+}
+
+impl Chirp {
+    pub fn value(&self, t: f32) -> f32 {
+        let t = t.rem_euclid(self.duration);
+        let (f0, f1) = (self.start_frequency, self.end_frequency);
+        let cycles = match self.sweep {
+            ChirpSweep::Exponential if f0 != f1 => {
+                let k = (f1 / f0).ln() / self.duration;
+                f0 * ((k * t).exp() - 1.0) / k
+            }
+            _ => f0 * t + (f1 - f0) * t * t / (2.0 * self.duration),
+        };
+        (2.0 * PI * cycles).sin()
+    }
+
+    /// The lowest and highest instantaneous frequency of the sweep.
+    pub fn frequency_range(&self) -> (f32, f32) {
+        (
+            self.start_frequency.min(self.end_frequency),
+            self.start_frequency.max(self.end_frequency),
+        )
+    }
+
+    /// The fraction of the sweep time during which the instantaneous frequency is above `frequency`.
+    pub fn fraction_above(&self, frequency: f32) -> f32 {
+        let (f_min, f_max) = self.frequency_range();
+        if frequency <= f_min {
+            return 1.0;
+        }
+        if frequency >= f_max {
+            return 0.0;
+        }
+        match self.sweep {
+            ChirpSweep::Linear => (f_max - frequency) / (f_max - f_min),
+            ChirpSweep::Exponential => (f_max / frequency).ln() / (f_max / f_min).ln(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModulationKind {
+    Am,
+    Fm,
+}
+
+impl ModulationKind {
+    pub const ALL: [ModulationKind; 2] = [ModulationKind::Am, ModulationKind::Fm];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModulationKind::Am => "AM",
+            ModulationKind::Fm => "FM",
+        }
+    }
+}
+
+/// A sine carrier, amplitude or frequency modulated by a sine.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Modulation {
+    pub kind: ModulationKind,
+    pub carrier_frequency: f32,
+    pub modulation_frequency: f32,
+    /// AM only, the modulation index between 0 and 1
+    pub depth: f32,
+    /// FM only, the peak frequency deviation in Hz
+    pub deviation: f32,
+}
+
+impl Default for Modulation {
+    fn default() -> Self {
+        Self {
+            kind: ModulationKind::Am,
+            carrier_frequency: 4.0,
+            modulation_frequency: 1.0,
+            depth: 0.5,
+            deviation: 2.0,
+        }
+    }
+}
+
+impl Modulation {
+    pub fn value(&self, t: f32) -> f32 {
+        let carrier_phase = 2.0 * PI * self.carrier_frequency * t;
+        let modulator = (2.0 * PI * self.modulation_frequency * t).sin();
+        match self.kind {
+            ModulationKind::Am => (1.0 + self.depth * modulator) * carrier_phase.sin(),
+            ModulationKind::Fm => {
+                let beta = self.deviation / self.modulation_frequency;
+                (carrier_phase + beta * modulator).sin()
+            }
+        }
+    }
+
+    /// The highest frequency of the signal, for FM this uses Carson's rule.
+    pub fn max_frequency(&self) -> f32 {
+        match self.kind {
+            ModulationKind::Am => self.carrier_frequency + self.modulation_frequency,
+            ModulationKind::Fm => {
+                self.carrier_frequency + self.deviation + self.modulation_frequency
+            }
+        }
+    }
+}
+
+/// How many tones make up the band of a [`Bandpass`] signal.
+const BANDPASS_TONES: usize = 9;
+
+/// A band of tones spread over `center_frequency ± bandwidth / 2`, to demonstrate undersampling.
+///
+/// The amplitude of the tones rises with their frequency,
+/// so that a band inverted by folding is recognizable in the FFT.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bandpass {
+    pub center_frequency: f32,
+    pub bandwidth: f32,
+}
+
+impl Default for Bandpass {
+    fn default() -> Self {
+        Self {
+            center_frequency: 15.0,
+            bandwidth: 2.0,
+        }
+    }
+}
+
+impl Bandpass {
+    /// The lowest and highest frequency of the band.
+    pub fn band(&self) -> (f32, f32) {
+        (
+            (self.center_frequency - self.bandwidth / 2.0).max(0.0),
+            self.center_frequency + self.bandwidth / 2.0,
+        )
+    }
+
+    pub fn value(&self, t: f32) -> f32 {
+        let (low, high) = self.band();
+        // the weights sum up to 1, so the peak amplitude stays below 1
+        let weight_sum = (BANDPASS_TONES * (BANDPASS_TONES + 1) / 2) as f32;
+        (0..BANDPASS_TONES)
+            .map(|i| {
+                let frequency = low + (high - low) * i as f32 / (BANDPASS_TONES - 1) as f32;
+                let amplitude = (i + 1) as f32 / weight_sum;
+                // Schroeder phases keep the tones from lining up into a large peak
+                let phase = PI * (i * i) as f32 / BANDPASS_TONES as f32;
+                amplitude * (2.0 * PI * frequency * t + phase).sin()
+            })
+            .sum()
+    }
+
+    /// The Nyquist zone the whole band lies in, or `None` if it straddles a zone boundary.
+    pub fn nyquist_zone(&self, sampling_frequency: f32) -> Option<u32> {
+        let nyquist_freq = sampling_frequency / 2.0;
+        let (low, high) = self.band();
+        let low_zone = nyquist_zone(low, sampling_frequency);
+        // the upper edge may touch the end of its zone
+        let high_zone = ((high / nyquist_freq).ceil() as u32).max(1);
+        (low_zone == high_zone).then_some(low_zone)
+    }
+
+    /// Folding mirrors the band in every even Nyquist zone.
+    pub fn is_inverted(&self, sampling_frequency: f32) -> bool {
+        nyquist_zone(self.center_frequency, sampling_frequency) % 2 == 0
+    }
+
+    /// The ranges of sampling frequencies `(zone, min, max)` that keep the whole band inside a
+    /// single Nyquist zone, so that it folds back without overlapping itself:
+    /// `2 * high / n <= fs <= 2 * low / (n - 1)`.
+    ///
+    /// Zone 1 is ordinary Nyquist sampling, its range is unbounded.
+    pub fn valid_sampling_ranges(&self) -> Vec<(u32, f32, f32)> {
+        let (low, high) = self.band();
+        let max_zone = (high / (high - low).max(f32::EPSILON)).floor() as u32;
+        (1..=max_zone.max(1))
+            .map(|n| {
+                let min_fs = 2.0 * high / n as f32;
+                let max_fs = if n == 1 {
+                    f32::INFINITY
+                } else {
+                    2.0 * low / (n - 1) as f32
+                };
+                (n, min_fs, max_fs)
+            })
+            .filter(|(_, min_fs, max_fs)| min_fs <= max_fs)
+            .collect()
+    }
+}
+
+/// A WAV file used as the continuous-time signal.
+#[derive(Clone, Debug, Default)]
+pub struct WavSource {
+    pub wav: Option<Arc<Wav>>,
+    pub name: String,
+    /// where the displayed window starts, in seconds into the file
+    pub start: f32,
+}
+
+impl PartialEq for WavSource {
+    fn eq(&self, other: &Self) -> bool {
+        // comparing the samples of large files on every frame would be too slow
+        let same_wav = match (&self.wav, &other.wav) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_wav && self.start == other.start
+    }
+}
+
+impl WavSource {
+    pub fn value(&self, t: f32) -> f32 {
+        match &self.wav {
+            Some(wav) => wav.value(self.start + t),
+            None => 0.0,
+        }
+    }
+}
+
+/// A complex exponential `amplitude * exp(j(2π * frequency * t + phase))`, the frequency may be negative.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IqTone {
+    pub frequency: f32,
+    pub amplitude: f32,
+    /// phase shift in units of π
+    pub phase: f32,
+}
+
+impl Default for IqTone {
+    fn default() -> Self {
+        Self {
+            frequency: 3.0,
+            amplitude: 1.0,
+            phase: 0.0,
+        }
+    }
+}
+
+impl IqTone {
+    /// The in-phase (real) and quadrature (imaginary) part at time `t` (in seconds).
+    pub fn value(&self, t: f32) -> (f32, f32) {
+        let theta = 2.0 * PI * self.frequency * t + self.phase * PI;
+        (self.amplitude * theta.cos(), self.amplitude * theta.sin())
+    }
+}
+
+/// A frequency in the spectrum of the signal that gets a marker in the FFT plot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectralLine {
+    pub label: Option<&'static str>,
+    pub frequency: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignalKind {
+    Components,
+    Chirp,
+    Modulated,
+    Wav,
+    Expression,
+    Iq,
+    Bandpass,
+}
+
+impl SignalKind {
+    pub const ALL: [SignalKind; 7] = [
+        SignalKind::Components,
+        SignalKind::Chirp,
+        SignalKind::Modulated,
+        SignalKind::Wav,
+        SignalKind::Expression,
+        SignalKind::Iq,
+        SignalKind::Bandpass,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignalKind::Components => "Components",
+            SignalKind::Chirp => "Chirp",
+            SignalKind::Modulated => "AM / FM",
+            SignalKind::Wav => "WAV file",
+            SignalKind::Expression => "Expression",
+            SignalKind::Iq => "IQ (complex)",
+            SignalKind::Bandpass => "Bandpass",
+        }
+    }
+}
+
+/// The continuous-time input signal of the selected `kind`,
+/// scaled by `amplitude` and shifted by `dc_offset`.
+///
+/// The settings of all kinds are kept, so that switching back and forth doesn't lose them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signal {
+    pub kind: SignalKind,
+    pub components: Vec<SignalComponent>,
+    pub chirp: Chirp,
+    pub modulation: Modulation,
+    pub wav: WavSource,
+    pub expression: Expression,
+    pub iq_tones: Vec<IqTone>,
+    pub bandpass: Bandpass,
+    pub amplitude: f32,
+    pub dc_offset: f32,
+    /// band-limited components only contain harmonics below this frequency,
+    /// this follows the Nyquist frequency of the sampler
+    pub band_limit: f32,
+}
+
+impl Default for Signal {
+    fn default() -> Self {
+        Self {
+            kind: SignalKind::Components,
+            components: vec![SignalComponent::default()],
+            chirp: Chirp::default(),
+            modulation: Modulation::default(),
+            wav: WavSource::default(),
+            expression: Expression::default(),
+            iq_tones: vec![IqTone::default()],
+            bandpass: Bandpass::default(),
+            amplitude: 1.0,
+            dc_offset: 0.0,
+            band_limit: 5.0,
+        }
+    }
+}
+
+impl Signal {
+    /// Whether the signal is complex, with a quadrature part next to the in-phase part.
+    pub fn is_complex(&self) -> bool {
+        self.kind == SignalKind::Iq
+    }
+
+    /// Evaluates the signal at time `t` (in seconds),
+    /// for a complex signal this is the in-phase (real) part.
+    pub fn value(&self, t: f32) -> f32 {
+        let y: f32 = match self.kind {
+            SignalKind::Components => self
+                .components
+                .iter()
+                .map(|c| c.value(t, self.band_limit))
+                .sum(),
+            SignalKind::Chirp => self.chirp.value(t),
+            SignalKind::Modulated => self.modulation.value(t),
+            SignalKind::Wav => self.wav.value(t),
+            SignalKind::Expression => self.expression.value(t),
+            SignalKind::Iq => self.iq_tones.iter().map(|tone| tone.value(t).0).sum(),
+            SignalKind::Bandpass => self.bandpass.value(t),
+        };
+        self.amplitude * y + self.dc_offset
+    }
+
+    /// Evaluates the quadrature (imaginary) part of the signal at time `t` (in seconds),
+    /// this is 0 for real signals. The DC offset is real, so it only shifts the in-phase part.
+    pub fn quadrature_value(&self, t: f32) -> f32 {
+        match self.kind {
+            SignalKind::Iq => {
+                self.amplitude
+                    * self
+                        .iq_tones
+                        .iter()
+                        .map(|tone| tone.value(t).1)
+                        .sum::<f32>()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// The components making up the signal, empty unless the kind is [`SignalKind::Components`].
+    pub fn active_components(&self) -> &[SignalComponent] {
+        match self.kind {
+            SignalKind::Components => &self.components,
+            _ => &[],
+        }
+    }
+
+    /// The AC power (variance) of the signal over the observation window of `window_duration`
+    /// seconds, for a complex signal the power of the in-phase part.
+    pub fn ac_power(&self, window_duration: f32) -> f32 {
+        // at least 8 points per period of the highest frequency, so that no tone aliases to DC
+        let n = ((8.0 * self.max_frequency() * window_duration) as usize).clamp(1000, 20_000);
+        let values: Vec<f32> = (0..n)
+            .map(|i| self.value(i as f32 / n as f32 * window_duration))
+            .collect();
+        let mean = values.iter().sum::<f32>() / n as f32;
+        values.iter().map(|y| (y - mean).powi(2)).sum::<f32>() / n as f32
+    }
+
+    /// The highest frequency contained in the signal.
+    pub fn max_frequency(&self) -> f32 {
+        match self.kind {
+            SignalKind::Components => self
+                .components
+                .iter()
+                .map(|c| c.max_frequency(self.band_limit))
+                .fold(0.0, f32::max),
+            SignalKind::Chirp => self.chirp.frequency_range().1,
+            SignalKind::Modulated => self.modulation.max_frequency(),
+            // the file may contain anything up to its own Nyquist frequency
+            SignalKind::Wav => match &self.wav.wav {
+                Some(wav) => wav.sample_rate as f32 / 2.0,
+                None => 0.0,
+            },
+            // the bandwidth of an arbitrary expression isn't known
+            SignalKind::Expression => 0.0,
+            SignalKind::Iq => self
+                .iq_tones
+                .iter()
+                .map(|tone| tone.frequency.abs())
+                .fold(0.0, f32::max),
+            SignalKind::Bandpass => self.bandpass.band().1,
+        }
+    }
+
+    /// The discrete frequencies of the signal worth marking: the fundamental of each component,
+    /// the carrier and the first pair of sidebands of a modulated signal, each complex tone,
+    /// or the edges of a bandpass signal.
+    pub fn spectral_lines(&self) -> Vec<SpectralLine> {
+        match self.kind {
+            SignalKind::Components => self
+                .components
+                .iter()
+                .map(|c| SpectralLine {
+                    label: None,
+                    frequency: c.frequency,
+                })
+                .collect(),
+            SignalKind::Iq => self
+                .iq_tones
+                .iter()
+                .map(|tone| SpectralLine {
+                    label: None,
+                    frequency: tone.frequency,
+                })
+                .collect(),
+            SignalKind::Bandpass => {
+                let (low, high) = self.bandpass.band();
+                vec![
+                    SpectralLine {
+                        label: Some("Low edge"),
+                        frequency: low,
+                    },
+                    SpectralLine {
+                        label: Some("High edge"),
+                        frequency: high,
+                    },
+                ]
+            }
+            SignalKind::Chirp | SignalKind::Wav | SignalKind::Expression => Vec::new(),
+            SignalKind::Modulated => {
+                let m = &self.modulation;
+                vec![
+                    SpectralLine {
+                        label: Some("Carrier"),
+                        frequency: m.carrier_frequency,
+                    },
+                    SpectralLine {
+                        label: Some("Lower sideband"),
+                        frequency: (m.carrier_frequency - m.modulation_frequency).abs(),
+                    },
+                    SpectralLine {
+                        label: Some("Upper sideband"),
+                        frequency: m.carrier_frequency + m.modulation_frequency,
+                    },
+                ]
+            }
+        }
+    }
+}
+
+/// Folds `frequency` back into the first Nyquist zone `[0, sampling_frequency / 2]`.
+pub fn alias_frequency(frequency: f32, sampling_frequency: f32) -> f32 {
+    let alias_freq = frequency % sampling_frequency;
+    if alias_freq > sampling_frequency / 2.0 {
+        sampling_frequency - alias_freq
+    } else {
+        alias_freq
+    }
+}
+
+/// The Nyquist zone `frequency` lies in, zone 1 is `[0, sampling_frequency / 2)`.
+pub fn nyquist_zone(frequency: f32, sampling_frequency: f32) -> u32 {
+    (frequency / (sampling_frequency / 2.0)).floor() as u32 + 1
+}
+
+/// Wraps `frequency` into `[-sampling_frequency / 2, sampling_frequency / 2)`.
+///
+/// Unlike a real signal, a complex one has no mirrored negative frequency to fold onto,
+/// so a tone above Nyquist reappears on the negative side.
+pub fn wrap_frequency(frequency: f32, sampling_frequency: f32) -> f32 {
+    (frequency + sampling_frequency / 2.0).rem_euclid(sampling_frequency) - sampling_frequency / 2.0
+}
+
+/// Folds the frequency band `[low, high]` back into the first Nyquist zone.
+///
+/// The band is split at every multiple of the Nyquist frequency, so the result contains one
+/// (possibly overlapping) range per Nyquist zone the band touches.
+pub fn alias_range(low: f32, high: f32, sampling_frequency: f32) -> Vec<(f32, f32)> {
+    let nyquist_freq = sampling_frequency / 2.0;
+    let mut ranges = Vec::new();
+    let mut start = low;
+    while start < high {
+        let zone_end = ((start / nyquist_freq).floor() + 1.0) * nyquist_freq;
+        let end = zone_end.min(high);
+        let a = alias_frequency(start, sampling_frequency);
+        // evaluate the end just inside the zone, it would fold to the wrong edge otherwise
+        let b = if end == zone_end {
+            if ((zone_end / nyquist_freq).round() as u32) % 2 == 1 {
+                nyquist_freq
+            } else {
+                0.0
+            }
+        } else {
+            alias_frequency(end, sampling_frequency)
+        };
+        ranges.push((a.min(b), a.max(b)));
+        start = end;
+    }
+    ranges
+}
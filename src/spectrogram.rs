@@ -0,0 +1,96 @@
+use egui::Color32;
+use rustfft::{FftPlanner, num_complex::Complex};
+
+use crate::window::{FftWindow, WindowKind};
+
+/// Settings of the short-time Fourier transform.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spectrogram {
+    pub enabled: bool,
+    /// samples per segment
+    pub segment_length: usize,
+    /// samples from the start of one segment to the next
+    pub hop: usize,
+    pub window: FftWindow,
+    /// how far below the strongest cell the colour map reaches, in dB
+    pub dynamic_range_db: f32,
+}
+
+impl Default for Spectrogram {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            segment_length: 32,
+            hop: 4,
+            window: FftWindow {
+                kind: WindowKind::Hann,
+                ..FftWindow::default()
+            },
+            dynamic_range_db: 60.0,
+        }
+    }
+}
+
+impl Spectrogram {
+    /// The segment length that fits into `n_samples`.
+    pub fn effective_segment_length(&self, n_samples: usize) -> usize {
+        self.segment_length.min(n_samples)
+    }
+
+    /// The magnitude in dB of each segment of `samples`, one column per segment with the bins
+    /// in FFT order. A tone with an amplitude of 1 is at about 0 dB in the bins next to it.
+    pub fn compute(
+        &self,
+        samples: &[Complex<f32>],
+        planner: &mut FftPlanner<f32>,
+    ) -> Vec<Vec<f32>> {
+        let segment_length = self.effective_segment_length(samples.len());
+        if segment_length < 2 {
+            return Vec::new();
+        }
+        let hop = self.hop.max(1);
+        let window = self.window.coefficients(segment_length);
+        // a tone of amplitude 1 splits into two bins of sum(window) / 2 each
+        let gain = window.iter().sum::<f32>() / 2.0;
+        // the planner keeps the plan, so that all segments share it
+        let fft = planner.plan_fft_forward(segment_length);
+
+        (0..=(samples.len() - segment_length) / hop)
+            .map(|column| {
+                let start = column * hop;
+                let mut segment: Vec<Complex<f32>> = samples[start..start + segment_length]
+                    .iter()
+                    .zip(&window)
+                    .map(|(x, w)| x * w)
+                    .collect();
+                fft.process(&mut segment);
+                segment
+                    .iter()
+                    .map(|bin| 20.0 * (bin.norm() / gain).max(1e-9).log10())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Maps `value` in 0..1 from black over purple and red to a pale yellow.
+pub fn colormap(value: f32) -> Color32 {
+    const STOPS: [(f32, [f32; 3]); 5] = [
+        (0.0, [0.0, 0.0, 0.0]),
+        (0.3, [80.0, 0.0, 120.0]),
+        (0.6, [220.0, 50.0, 30.0]),
+        (0.85, [255.0, 200.0, 0.0]),
+        (1.0, [255.0, 255.0, 200.0]),
+    ];
+    let value = value.clamp(0.0, 1.0);
+    let i = STOPS
+        .iter()
+        .position(|(stop, _)| *stop >= value)
+        .unwrap_or(STOPS.len() - 1)
+        .max(1);
+    let (low, low_color) = STOPS[i - 1];
+    let (high, high_color) = STOPS[i];
+    let fraction = (value - low) / (high - low);
+    let channel = |c: usize| (low_color[c] + (high_color[c] - low_color[c]) * fraction) as u8;
+    Color32::from_rgb(channel(0), channel(1), channel(2))
+}
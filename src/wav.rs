@@ -0,0 +1,144 @@
+/// A decoded WAV file, mixed down to a single channel.
+#[derive(Debug)]
+pub struct Wav {
+    pub sample_rate: u32,
+    /// the samples, normalized to [-1, 1]
+    pub samples: Vec<f32>,
+}
+
+impl Wav {
+    /// The length of the file in seconds.
+    pub fn duration(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate as f32
+    }
+
+    /// Evaluates the file at time `t` (in seconds) by linear interpolation,
+    /// the file is silent outside of its duration.
+    pub fn value(&self, t: f32) -> f32 {
+        let position = t * self.sample_rate as f32;
+        if position < 0.0 {
+            return 0.0;
+        }
+        let i = position.floor() as usize;
+        let fraction = position - i as f32;
+        match (self.samples.get(i), self.samples.get(i + 1)) {
+            (Some(a), Some(b)) => a + (b - a) * fraction,
+            (Some(a), None) => *a,
+            _ => 0.0,
+        }
+    }
+}
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+/// Decodes an uncompressed WAV file (8, 16, 24 or 32 bit integer PCM, or 32 bit float).
+pub fn decode(bytes: &[u8]) -> Result<Wav, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("not a RIFF/WAVE file".to_string());
+    }
+
+    // (format, channels, sample rate, bits per sample)
+    let mut format = None;
+    let mut data = None;
+
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let size = read_u32(bytes, offset + 4) as usize;
+        let body_start = offset + 8;
+        // the data chunk of a truncated file may claim more bytes than there are,
+        // any other chunk running past the end is corrupt and ends the file
+        let body_end = match body_start.checked_add(size) {
+            Some(end) if end <= bytes.len() => end,
+            _ if id == b"data" => bytes.len(),
+            _ => break,
+        };
+        let body = &bytes[body_start..body_end];
+
+        match id {
+            b"fmt " => {
+                if body.len() < 16 {
+                    return Err("fmt chunk is too short".to_string());
+                }
+                let mut audio_format = read_u16(body, 0);
+                if audio_format == WAVE_FORMAT_EXTENSIBLE && body.len() >= 26 {
+                    // the first two bytes of the sub format GUID are the actual format
+                    audio_format = read_u16(body, 24);
+                }
+                format = Some((
+                    audio_format,
+                    read_u16(body, 2),
+                    read_u32(body, 4),
+                    read_u16(body, 14),
+                ));
+            }
+            b"data" => data = Some(body),
+            _ => {}
+        }
+
+        // chunks are padded to an even size, nothing follows a chunk that reaches the end
+        offset = body_end.saturating_add(size % 2);
+        if body_end == bytes.len() {
+            break;
+        }
+    }
+
+    let (audio_format, channels, sample_rate, bits_per_sample) =
+        format.ok_or_else(|| "missing fmt chunk".to_string())?;
+    let data = data.ok_or_else(|| "missing data chunk".to_string())?;
+
+    if channels == 0 || sample_rate == 0 {
+        return Err("invalid fmt chunk".to_string());
+    }
+
+    let decode_sample: fn(&[u8]) -> f32 = match (audio_format, bits_per_sample) {
+        (WAVE_FORMAT_PCM, 8) => |b| (b[0] as f32 - 128.0) / 128.0,
+        (WAVE_FORMAT_PCM, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+        (WAVE_FORMAT_PCM, 24) => {
+            |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8388608.0
+        }
+        (WAVE_FORMAT_PCM, 32) => {
+            |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2147483648.0
+        }
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        _ => {
+            return Err(format!(
+                "unsupported format {audio_format} with {bits_per_sample} bits per sample"
+            ));
+        }
+    };
+
+    let bytes_per_sample = bits_per_sample as usize / 8;
+    let frame_size = bytes_per_sample * channels as usize;
+
+    let samples = data
+        .chunks_exact(frame_size)
+        .map(|frame| {
+            let sum: f32 = frame
+                .chunks_exact(bytes_per_sample)
+                .map(decode_sample)
+                .sum();
+            sum / channels as f32
+        })
+        .collect();
+
+    Ok(Wav {
+        sample_rate,
+        samples,
+    })
+}
@@ -0,0 +1,177 @@
+use rustfft::{FftPlanner, num_complex::Complex};
+use std::f32::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowKind {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+    BlackmanHarris,
+    FlatTop,
+    Kaiser,
+    Gaussian,
+}
+
+impl WindowKind {
+    pub const ALL: [WindowKind; 8] = [
+        WindowKind::Rectangular,
+        WindowKind::Hann,
+        WindowKind::Hamming,
+        WindowKind::Blackman,
+        WindowKind::BlackmanHarris,
+        WindowKind::FlatTop,
+        WindowKind::Kaiser,
+        WindowKind::Gaussian,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowKind::Rectangular => "Rectangular",
+            WindowKind::Hann => "Hann",
+            WindowKind::Hamming => "Hamming",
+            WindowKind::Blackman => "Blackman",
+            WindowKind::BlackmanHarris => "Blackman-Harris",
+            WindowKind::FlatTop => "Flat-top",
+            WindowKind::Kaiser => "Kaiser",
+            WindowKind::Gaussian => "Gaussian",
+        }
+    }
+}
+
+/// The window the FFT input is multiplied with to reduce spectral leakage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FftWindow {
+    pub kind: WindowKind,
+    /// Kaiser only, the shape parameter β
+    pub kaiser_beta: f32,
+    /// Gaussian only, the standard deviation relative to half the window length
+    pub gaussian_sigma: f32,
+}
+
+impl Default for FftWindow {
+    fn default() -> Self {
+        Self {
+            kind: WindowKind::Rectangular,
+            kaiser_beta: 8.6,
+            gaussian_sigma: 0.4,
+        }
+    }
+}
+
+/// How a window trades resolution for leakage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowMetrics {
+    /// equivalent noise bandwidth in bins
+    pub enbw: f32,
+    /// the mean of the window, the factor it scales a tone's amplitude by
+    pub coherent_gain: f32,
+    /// in dB, how much a tone halfway between two bins loses
+    pub scalloping_loss_db: f32,
+    /// in dB relative to the main lobe
+    pub highest_sidelobe_db: f32,
+}
+
+/// The modified Bessel function of the first kind and order 0, as a power series.
+fn bessel_i0(x: f32) -> f32 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    for k in 1..50 {
+        term *= (x / (2.0 * k as f32)).powi(2);
+        sum += term;
+        if term < 1e-10 * sum {
+            break;
+        }
+    }
+    sum
+}
+
+/// A sum of cosines, `a0 - a1 cos(x) + a2 cos(2x) - ...`.
+fn cosine_sum(coefficients: &[f32], x: f32) -> f32 {
+    coefficients
+        .iter()
+        .enumerate()
+        .map(|(k, a)| if k % 2 == 0 { 1.0 } else { -1.0 } * a * (k as f32 * x).cos())
+        .sum()
+}
+
+/// The lengths the metrics are measured with, a window of `METRICS_LENGTH` samples
+/// zero padded to `METRICS_FFT_SIZE` to resolve its sidelobes
+const METRICS_LENGTH: usize = 128;
+const METRICS_FFT_SIZE: usize = 128 * 32;
+
+impl FftWindow {
+    /// The window for `n` samples, symmetric around its middle.
+    pub fn coefficients(&self, n: usize) -> Vec<f32> {
+        if n < 2 {
+            return vec![1.0; n];
+        }
+        let last = (n - 1) as f32;
+        (0..n)
+            .map(|i| {
+                let x = 2.0 * PI * i as f32 / last;
+                // the position relative to the middle, in -1..1
+                let r = 2.0 * i as f32 / last - 1.0;
+                match self.kind {
+                    WindowKind::Rectangular => 1.0,
+                    WindowKind::Hann => cosine_sum(&[0.5, 0.5], x),
+                    WindowKind::Hamming => cosine_sum(&[0.54, 0.46], x),
+                    WindowKind::Blackman => cosine_sum(&[0.42, 0.5, 0.08], x),
+                    WindowKind::BlackmanHarris => {
+                        cosine_sum(&[0.35875, 0.48829, 0.14128, 0.01168], x)
+                    }
+                    WindowKind::FlatTop => cosine_sum(
+                        &[0.21557895, 0.41663158, 0.27726316, 0.08357895, 0.00694737],
+                        x,
+                    ),
+                    WindowKind::Kaiser => {
+                        bessel_i0(self.kaiser_beta * (1.0 - r * r).max(0.0).sqrt())
+                            / bessel_i0(self.kaiser_beta)
+                    }
+                    WindowKind::Gaussian => (-0.5 * (r / self.gaussian_sigma).powi(2)).exp(),
+                }
+            })
+            .collect()
+    }
+
+    /// Measures the window on its zero padded spectrum.
+    pub fn metrics(&self, planner: &mut FftPlanner<f32>) -> WindowMetrics {
+        let window = self.coefficients(METRICS_LENGTH);
+        let sum: f32 = window.iter().sum();
+        let sum_sqr: f32 = window.iter().map(|w| w * w).sum();
+
+        let mut spectrum: Vec<Complex<f32>> = window
+            .iter()
+            .map(|w| Complex::new(*w, 0.0))
+            .chain(std::iter::repeat(Complex::new(0.0, 0.0)))
+            .take(METRICS_FFT_SIZE)
+            .collect();
+        planner
+            .plan_fft_forward(METRICS_FFT_SIZE)
+            .process(&mut spectrum);
+        let magnitudes: Vec<f32> = spectrum[..METRICS_FFT_SIZE / 2]
+            .iter()
+            .map(|c| c.norm() / sum)
+            .collect();
+
+        // half a bin of the unpadded window
+        let oversampling = METRICS_FFT_SIZE / METRICS_LENGTH;
+        let scalloping = magnitudes[oversampling / 2];
+
+        // the main lobe ends at the first minimum, the sidelobes come after it
+        let main_lobe_end = (1..magnitudes.len() - 1)
+            .find(|&i| magnitudes[i] <= magnitudes[i - 1] && magnitudes[i] <= magnitudes[i + 1])
+            .unwrap_or(magnitudes.len() - 1);
+        let highest_sidelobe = magnitudes[main_lobe_end..]
+            .iter()
+            .copied()
+            .fold(0.0, f32::max);
+
+        WindowMetrics {
+            enbw: METRICS_LENGTH as f32 * sum_sqr / (sum * sum),
+            coherent_gain: sum / METRICS_LENGTH as f32,
+            scalloping_loss_db: -20.0 * scalloping.log10(),
+            highest_sidelobe_db: 20.0 * highest_sidelobe.max(1e-12).log10(),
+        }
+    }
+}